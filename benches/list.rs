@@ -0,0 +1,109 @@
+use std::hint::black_box;
+use std::time::Duration;
+use std::time::Instant;
+
+use vec_list::VecList;
+
+const N: usize = 1_000_000;
+const ROUNDS: u32 = 10;
+
+fn bench<F: FnMut()>(name: &str, mut f: F) {
+    /* warm up */
+    f();
+
+    let mut total = Duration::ZERO;
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        f();
+        total += start.elapsed();
+    }
+
+    println!("{:<24} {:>12?}", name, total / ROUNDS);
+}
+
+fn filled() -> VecList<u64> {
+    let mut list = VecList::with_capacity(N);
+    for i in 0..N as u64 {
+        list.push_back(i);
+    }
+    list
+}
+
+/* every other slot is deleted and refilled from the front, so links jump around */
+fn fragmented() -> VecList<u64> {
+    let mut list = filled();
+    for idx in (0..N).step_by(2) {
+        list.delete(idx);
+    }
+    for i in 0..(N / 2) as u64 {
+        list.push_front(i);
+    }
+    list
+}
+
+fn main() {
+    bench("push_back", || {
+        black_box(filled());
+    });
+
+    bench("extend", || {
+        let mut list = VecList::new();
+        list.extend(0..N as u64);
+        black_box(list);
+    });
+
+    bench("Vec::extend", || {
+        let mut vec = Vec::new();
+        vec.extend(0..N as u64);
+        black_box(vec);
+    });
+
+    let list = filled();
+    bench("iter", || {
+        black_box(list.values().sum::<u64>());
+    });
+
+    bench("clone", || {
+        black_box(list.clone());
+    });
+
+    bench("clone_copy", || {
+        black_box(list.clone_copy());
+    });
+
+    let list = fragmented();
+    bench("iter fragmented", || {
+        black_box(list.values().sum::<u64>());
+    });
+
+    bench("iter fragmented prefetch", || {
+        black_box(list.iter().prefetch().map(|(val, _)| val).sum::<u64>());
+    });
+
+    let mut list = filled();
+    bench("index_at_position", || {
+        for pos in (0..N).step_by(N / 1000) {
+            black_box(list.index_at_position(pos));
+        }
+    });
+
+    list.set_position_index(true);
+    bench("index_at_position indexed", || {
+        for pos in (0..N).step_by(N / 1000) {
+            black_box(list.index_at_position(pos));
+        }
+    });
+
+    bench("insert_at_position indexed", || {
+        for pos in (0..N).step_by(N / 1000) {
+            list.insert_at_position(pos, 0);
+        }
+    });
+
+    bench("push_back + pop_front", || {
+        let mut list = filled();
+        while let Some(val) = list.pop_front() {
+            black_box(val);
+        }
+    });
+}
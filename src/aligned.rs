@@ -0,0 +1,46 @@
+use std::ops;
+
+use crate::VecList;
+
+/// A value on its own 64-byte cache line, so two threads writing neighbor values
+/// through disjoint `&mut` don't bounce the same line between cores.
+///
+/// Only the values are padded, the links stay packed, they are written only with `&mut VecList`.
+#[repr(align(64))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CacheAligned<T>(pub T);
+
+/// A `VecList` whose values each take at least one cache line
+pub type AlignedVecList<T> = VecList<CacheAligned<T>>;
+
+impl<T> CacheAligned<T> {
+    pub const fn new(val: T) -> Self {
+        Self(val)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for CacheAligned<T> {
+    fn from(val: T) -> Self {
+        Self(val)
+    }
+}
+
+impl<T> ops::Deref for CacheAligned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> ops::DerefMut for CacheAligned<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+const _: () = assert!(std::mem::align_of::<CacheAligned<u8>>() == 64);
@@ -0,0 +1,90 @@
+use std::sync::Mutex;
+
+use tokio::sync::Notify;
+
+use crate::BoundedList;
+use crate::EvictionPolicy;
+
+/// A `BoundedList` shared by tasks, `pop` waits for an element, and `push` evicts or rejects
+/// by the `EvictionPolicy` while `push_wait` waits for room instead, so it works as a
+/// channel for pipelines in one process. Any runtime can drive it.
+#[derive(Debug)]
+pub struct AsyncBoundedQueue<T> {
+    /* never locked across an await */
+    list: Mutex<BoundedList<T>>,
+    /* a permit per push, so a pop which checked just before isn't missed */
+    items: Notify,
+    /* a permit per pop, for `push_wait` */
+    room: Notify,
+}
+
+impl<T> AsyncBoundedQueue<T> {
+    pub fn new(cap: usize) -> Self {
+        Self::with_policy(cap, EvictionPolicy::default())
+    }
+
+    pub fn with_policy(cap: usize, policy: EvictionPolicy) -> Self {
+        Self {
+            list: Mutex::new(BoundedList::with_policy(cap, policy)),
+            items: Notify::new(),
+            room: Notify::new(),
+        }
+    }
+
+    /// O(1), never waits, false if the policy rejected it
+    pub fn push(&self, val: T) -> bool {
+        let added = self.list.lock().unwrap().add(val).is_some();
+        if added {
+            self.items.notify_one();
+        }
+        added
+    }
+
+    /// O(1), gives the value back when it's full
+    pub fn try_push(&self, val: T) -> Result<(), T> {
+        self.list.lock().unwrap().try_add(val)?;
+        self.items.notify_one();
+        Ok(())
+    }
+
+    /// Waits for room and never evicts, so a slow consumer slows the producers down.
+    /// Never finishes if the cap is 0.
+    pub async fn push_wait(&self, mut val: T) {
+        loop {
+            match self.try_push(val) {
+                Ok(()) => return,
+                Err(back) => val = back,
+            }
+            self.room.notified().await;
+        }
+    }
+
+    /// O(1), the front one if any
+    pub fn try_pop(&self) -> Option<T> {
+        let val = self.list.lock().unwrap().pop_front()?;
+        self.room.notify_one();
+        Some(val)
+    }
+
+    /// Waits for the front one
+    pub async fn pop(&self) -> T {
+        loop {
+            if let Some(val) = self.try_pop() {
+                return val;
+            }
+            self.items.notified().await;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.list.lock().unwrap().capacity()
+    }
+}
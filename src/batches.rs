@@ -0,0 +1,143 @@
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ptr;
+
+use crate::Link;
+use crate::Storage;
+use crate::VecList;
+
+/// An iterator whose items borrow the iterator itself, so only one lives at a time
+pub trait LendingIterator {
+    type Item<'b>
+    where
+        Self: 'b;
+
+    fn next(&mut self) -> Option<Self::Item<'_>>;
+}
+
+/// Up to `N` consecutive elements at a time in list order, from `VecList::batches_mut`
+pub struct BatchesMut<'a, T, const N: usize> {
+    links: &'a [Link],
+    /* raw, like `IterMut` */
+    vals: *mut MaybeUninit<T>,
+    next: Option<usize>,
+    /* the pointers of the current batch, lent out as `&mut T`s */
+    batch: [*mut T; N],
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<T, S: Storage<T>> VecList<T, S> {
+    /// Lends `&mut [&mut T]`s of `N` elements in list order, the last one may be shorter.
+    /// The batch is gathered on the stack, nothing is allocated.
+    pub fn batches_mut<const N: usize>(&mut self) -> BatchesMut<'_, T, N> {
+        const { assert!(N > 0, "batches can't be empty!") };
+
+        let head = self.head;
+        let (links, vals) = self.storage.parts_mut();
+
+        BatchesMut {
+            links,
+            vals: vals.as_mut_ptr(),
+            next: head,
+            batch: [ptr::null_mut(); N],
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> BatchesMut<'a, T, N> {
+    /// Runs `f` on every batch
+    pub fn for_each<F: FnMut(&mut [&mut T])>(mut self, mut f: F) {
+        while let Some(batch) = LendingIterator::next(&mut self) {
+            f(batch);
+        }
+    }
+}
+
+impl<'a, T, const N: usize> LendingIterator for BatchesMut<'a, T, N> {
+    type Item<'b>
+        = &'b mut [&'b mut T]
+    where
+        Self: 'b;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        let mut len = 0;
+        while len < N {
+            let Some(idx) = self.next else {
+                break;
+            };
+
+            let link = unsafe { self.links.get_unchecked(idx) };
+
+            debug_assert!(link.has_value());
+
+            self.next = link.next();
+            self.batch[len] = unsafe { (*self.vals.add(idx)).as_mut_ptr() };
+            len += 1;
+        }
+
+        if len == 0 {
+            return None;
+        }
+
+        /* distinct live slots, and `&mut T` has the layout of `*mut T` */
+        let batch = ptr::slice_from_raw_parts_mut(self.batch.as_mut_ptr().cast(), len);
+        Some(unsafe { &mut *batch })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* the batch lengths, and every value gets its batch number added */
+    fn batch_lens<const N: usize>(list: &mut VecList<u32>) -> Vec<usize> {
+        let mut lens = Vec::new();
+        list.batches_mut::<N>().for_each(|batch| {
+            for val in batch.iter_mut() {
+                **val += 100 * lens.len() as u32;
+            }
+            lens.push(batch.len());
+        });
+        lens
+    }
+
+    #[test]
+    fn the_last_batch_is_shorter() {
+        let mut list: VecList<u32> = (0..7).collect();
+        assert_eq!(batch_lens::<3>(&mut list), [3, 3, 1]);
+        assert!(list.values().eq(&[0, 1, 2, 103, 104, 105, 206]));
+
+        let mut list: VecList<u32> = (0..6).collect();
+        assert_eq!(batch_lens::<3>(&mut list), [3, 3]);
+    }
+
+    #[test]
+    fn batches_of_one() {
+        let mut list: VecList<u32> = (0..3).collect();
+        assert_eq!(batch_lens::<1>(&mut list), [1, 1, 1]);
+        assert!(list.values().eq(&[0, 101, 202]));
+    }
+
+    #[test]
+    fn an_empty_list_has_no_batches() {
+        let mut list = VecList::new();
+        assert!(batch_lens::<4>(&mut list).is_empty());
+        assert!(LendingIterator::next(&mut list.batches_mut::<4>()).is_none());
+    }
+
+    #[test]
+    fn batches_follow_list_order() {
+        let mut list: VecList<u32> = (0..6).collect();
+        list.delete(1);
+        list.delete(4);
+        list.swap_links(0, 5);
+        /* reuses slot 4, at the front */
+        list.push_front(9);
+        assert!(list.values().eq(&[9, 5, 2, 3, 0]));
+
+        assert_eq!(batch_lens::<2>(&mut list), [2, 2, 1]);
+        assert!(list.values().eq(&[9, 5, 102, 103, 200]));
+        crate::tests::check(&list);
+    }
+}
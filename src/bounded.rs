@@ -1,30 +1,237 @@
+use core::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::iter::Rev;
+use std::iter::Take;
+use std::ops;
+
+use crate::ArrayStorage;
+use crate::Drain;
+use crate::IntoIter;
 use crate::Iter;
+use crate::IterMut;
+use crate::Storage;
 use crate::VecList;
+use crate::VecStorage;
+
+/// What `BoundedList::add` does when cap is full
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EvictionPolicy {
+    /// `pop_front` the oldest, then `push_back` the new one
+    #[default]
+    EvictOldest,
+    /// `pop_back` the newest, then `push_back` the new one
+    EvictNewest,
+    /// Drop the new one
+    Reject,
+}
+
+/// Called with every element a `BoundedList` drops on its own
+pub trait OnEvict<T> {
+    fn on_evict(&mut self, val: T);
+}
+
+/// Just drops the evicted elements
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DropEvicted;
+
+impl<T> OnEvict<T> for DropEvicted {
+    fn on_evict(&mut self, _: T) {}
+}
+
+impl<T, F: FnMut(T)> OnEvict<T> for F {
+    fn on_evict(&mut self, val: T) {
+        self(val)
+    }
+}
 
-/// A bounded list, when cap is full, it will `pop_back` and `push_front`
-#[derive(Debug, Default, Clone)]
-pub struct BoundedList<T> {
-    list: VecList<T>,
+/// A bounded list, elements are added to the back, and when cap is full,
+/// the `EvictionPolicy` decides which one is dropped and hands it to `E`.
+/// It runs on any `Storage`, `ArrayBoundedList` never allocates.
+///
+/// The front is always the oldest and the back is always the newest, no matter how many
+/// times the freed slots have been reused:
+///
+/// ```
+/// use vec_list::BoundedList;
+///
+/// let mut list = BoundedList::new(3);
+/// for i in 0..3 {
+///     list.add(i);
+/// }
+/// assert_eq!(list.iter().map(|(x, _)| *x).collect::<Vec<_>>(), [0, 1, 2]);
+///
+/// /* first wrap */
+/// list.add(3);
+/// assert_eq!(list.iter().map(|(x, _)| *x).collect::<Vec<_>>(), [1, 2, 3]);
+///
+/// /* many wraps */
+/// for i in 4..10 {
+///     list.add(i);
+/// }
+/// assert_eq!(list.iter().map(|(x, _)| *x).collect::<Vec<_>>(), [7, 8, 9]);
+/// assert_eq!(list.iter().rev().map(|(x, _)| *x).collect::<Vec<_>>(), [9, 8, 7]);
+/// assert_eq!(list.front().map(|(x, _)| *x), Some(7));
+/// assert_eq!(list.back().map(|(x, _)| *x), Some(9));
+/// ```
+pub struct BoundedList<T, E = DropEvicted, S: Storage<T> = VecStorage<T>> {
+    list: VecList<T, S>,
     cap: usize,
+    policy: EvictionPolicy,
+    on_evict: E,
 }
 
+/// A `BoundedList` on `N` inline slots, it never allocates
+pub type ArrayBoundedList<T, const N: usize> = BoundedList<T, DropEvicted, ArrayStorage<T, N>>;
+
 impl<T> BoundedList<T> {
     pub fn new(cap: usize) -> Self {
+        Self::with_policy(cap, EvictionPolicy::default())
+    }
+
+    pub fn with_policy(cap: usize, policy: EvictionPolicy) -> Self {
+        Self::with_on_evict(cap, policy, DropEvicted)
+    }
+
+    /// Every item goes through `add`, so only the last `cap` ones are kept by default
+    pub fn from_iter_with_cap<I: IntoIterator<Item = T>>(iter: I, cap: usize) -> Self {
+        let mut bounded = Self::new(cap);
+        bounded.extend(iter);
+        bounded
+    }
+}
+
+impl<T, S: Storage<T>> BoundedList<T, DropEvicted, S> {
+    /// Runs on `storage`, whatever it holds is forgotten,
+    /// panics if `cap` is more than the storage can ever hold
+    pub fn with_storage(cap: usize, storage: S) -> Self {
+        Self::with_storage_and_on_evict(cap, EvictionPolicy::default(), storage, DropEvicted)
+    }
+}
+
+impl<T, E: OnEvict<T>> BoundedList<T, E> {
+    /// `on_evict` gets the elements evicted by `add` and `set_capacity`, and the ones rejected by
+    /// `EvictionPolicy::Reject`, but not the ones which are popped, drained or cleared
+    pub fn with_on_evict(cap: usize, policy: EvictionPolicy, on_evict: E) -> Self {
         Self {
             cap,
+            policy,
+            on_evict,
             list: VecList::with_capacity(cap),
         }
     }
+}
 
-    pub fn add(&mut self, val: T) {
-        if self.list.len() == self.cap {
-            self.list.pop_back();
-            self.list.push_front(val);
-        } else {
-            self.list.push_back(val);
+impl<T, E: OnEvict<T>, S: Storage<T>> BoundedList<T, E, S> {
+    /// `with_on_evict` on `storage`, whatever it holds is forgotten,
+    /// panics if `cap` is more than the storage can ever hold
+    pub fn with_storage_and_on_evict(
+        cap: usize,
+        policy: EvictionPolicy,
+        storage: S,
+        on_evict: E,
+    ) -> Self {
+        assert!(
+            cap <= storage.max_capacity(),
+            "cap is too large for the storage!"
+        );
+
+        Self {
+            cap,
+            policy,
+            on_evict,
+            list: VecList::with_storage(storage),
         }
     }
 
+    pub fn policy(&self) -> EvictionPolicy {
+        self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: EvictionPolicy) {
+        self.policy = policy;
+    }
+
+    /// Returns the slot index of the new one, which is valid until it's evicted,
+    /// or `None` if it was rejected
+    pub fn add(&mut self, val: T) -> Option<usize> {
+        if self.cap == 0 {
+            self.on_evict.on_evict(val);
+            return None;
+        }
+
+        if self.is_full() {
+            let evicted = match self.policy {
+                EvictionPolicy::EvictOldest => self.list.pop_front(),
+                EvictionPolicy::EvictNewest => self.list.pop_back(),
+                EvictionPolicy::Reject => {
+                    self.on_evict.on_evict(val);
+                    return None;
+                }
+            };
+
+            if let Some(evicted) = evicted {
+                self.on_evict.on_evict(evicted);
+            }
+        }
+
+        Some(self.list.push_back(val))
+    }
+
+    /// Never evicts, gives the value back when cap is full
+    pub fn try_add(&mut self, val: T) -> Result<usize, T> {
+        if self.is_full() {
+            return Err(val);
+        }
+
+        Ok(self.list.push_back(val))
+    }
+
+    /// O(1), removes by the slot index returned from `add`
+    pub fn remove(&mut self, idx: usize) -> Option<T> {
+        self.list.delete(idx)
+    }
+
+    /// O(1), the oldest
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    /// O(1), the newest
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+
+    /// O(1), the oldest
+    pub fn front(&self) -> Option<(&T, usize)> {
+        self.list.front()
+    }
+
+    /// O(1), the newest
+    pub fn back(&self) -> Option<(&T, usize)> {
+        self.list.back()
+    }
+
+    /// O(1), the oldest
+    pub fn front_mut(&mut self) -> Option<(&mut T, usize)> {
+        self.list.front_mut()
+    }
+
+    /// O(1), the newest
+    pub fn back_mut(&mut self) -> Option<(&mut T, usize)> {
+        self.list.back_mut()
+    }
+
+    /// Get by the slot index of the underlying `VecList`
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        self.list.get(idx)
+    }
+
+    /// Get by the slot index of the underlying `VecList`
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        self.list.get_mut(idx)
+    }
+
     pub fn len(&self) -> usize {
         self.list.len()
     }
@@ -33,7 +240,486 @@ impl<T> BoundedList<T> {
         self.len() == 0
     }
 
-    pub fn iter(&self) -> Iter<T> {
+    /// The bound given to `new`
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// When shrinking, the oldest ones are evicted,
+    /// panics if `cap` is more than the storage can ever hold
+    pub fn set_capacity(&mut self, cap: usize) {
+        assert!(
+            cap <= self.list.storage.max_capacity(),
+            "cap is too large for the storage!"
+        );
+
+        while self.len() > cap {
+            if let Some(evicted) = self.list.pop_front() {
+                self.on_evict.on_evict(evicted);
+            }
+        }
+        self.cap = cap;
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == self.cap
+    }
+
+    pub fn remaining_capacity(&self) -> usize {
+        self.cap - self.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+
+    /// O(n), keeps the order
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.list.retain(f);
+    }
+
+    /// Oldest first
+    pub fn drain(&mut self) -> Drain<'_, T, S> {
+        self.list.drain()
+    }
+
+    /// Oldest first, use `rev` for newest first
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.list.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.list.iter_mut()
+    }
+
+    /// O(n), the oldest matched one
+    pub fn find<P: FnMut(&T) -> bool>(&self, mut pred: P) -> Option<&T> {
+        self.list.values().find(|val| pred(val))
+    }
+
+    /// The `n` oldest ones, oldest first
+    pub fn oldest(&self, n: usize) -> Take<Iter<'_, T>> {
+        self.iter().take(n)
+    }
+
+    /// The `n` newest ones, newest first
+    pub fn latest(&self, n: usize) -> Take<Rev<Iter<'_, T>>> {
+        self.iter().rev().take(n)
+    }
+
+    pub fn as_list(&self) -> &VecList<T, S> {
+        &self.list
+    }
+
+    /// Drops the bound
+    pub fn into_inner(self) -> VecList<T, S> {
+        self.list
+    }
+}
+
+impl<T: fmt::Debug, E: fmt::Debug, S: Storage<T>> fmt::Debug for BoundedList<T, E, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoundedList")
+            .field("list", &self.list)
+            .field("cap", &self.cap)
+            .field("policy", &self.policy)
+            .field("on_evict", &self.on_evict)
+            .finish()
+    }
+}
+
+/// A cap of 0, nothing is ever kept
+impl<T, E: Default, S: Storage<T>> Default for BoundedList<T, E, S> {
+    fn default() -> Self {
+        Self {
+            list: VecList::default(),
+            cap: 0,
+            policy: EvictionPolicy::default(),
+            on_evict: E::default(),
+        }
+    }
+}
+
+impl<T: Clone, E: Clone, S: Storage<T>> Clone for BoundedList<T, E, S> {
+    fn clone(&self) -> Self {
+        Self {
+            list: self.list.clone(),
+            cap: self.cap,
+            policy: self.policy,
+            on_evict: self.on_evict.clone(),
+        }
+    }
+}
+
+impl<T, E: OnEvict<T>, S: Storage<T>> ops::Index<usize> for BoundedList<T, E, S> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("invalid key!")
+    }
+}
+
+impl<T, E: OnEvict<T>, S: Storage<T>> ops::IndexMut<usize> for BoundedList<T, E, S> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("invalid key!")
+    }
+}
+
+impl<T, E, S: Storage<T>> BoundedList<T, E, S> {
+    /// O(n), a new list of `f` of each one, with the same cap and policy, oldest first.
+    /// The indices are new.
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> BoundedList<U> {
+        let mut mapped = BoundedList::with_policy(self.cap, self.policy);
+        for val in self.list.values().map(f) {
+            mapped.list.push_back(val);
+        }
+        mapped
+    }
+}
+
+impl<T: Clone, E, S: Storage<T>> BoundedList<T, E, S> {
+    /// O(n), the values oldest first
+    pub fn to_vec(&self) -> Vec<T> {
+        self.list.values().cloned().collect()
+    }
+
+    /// O(n), a new list of the ones matching `pred`, with the same cap and policy, oldest first.
+    /// The indices are new.
+    pub fn filtered<P: FnMut(&T) -> bool>(&self, mut pred: P) -> BoundedList<T> {
+        let mut filtered = BoundedList::with_policy(self.cap, self.policy);
+        for val in self.list.values().filter(|val| pred(val)) {
+            filtered.list.push_back(val.clone());
+        }
+        filtered
+    }
+}
+
+impl<T: PartialEq, E, S: Storage<T>> BoundedList<T, E, S> {
+    /// O(n)
+    pub fn contains(&self, x: &T) -> bool {
+        self.list.contains(x)
+    }
+}
+
+/// Compares the cap and the values from oldest to newest
+impl<T: PartialEq, E, S: Storage<T>> PartialEq for BoundedList<T, E, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cap == other.cap && self.list == other.list
+    }
+}
+
+impl<T: Eq, E, S: Storage<T>> Eq for BoundedList<T, E, S> {}
+
+impl<T: Hash, E, S: Storage<T>> Hash for BoundedList<T, E, S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.cap.hash(state);
+        self.list.hash(state);
+    }
+}
+
+impl<T: fmt::Debug, E, S: Storage<T>> fmt::Display for BoundedList<T, E, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.list, f)
+    }
+}
+
+impl<T, E: OnEvict<T>, S: Storage<T>> Extend<T> for BoundedList<T, E, S> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.add(val);
+        }
+    }
+}
+
+/// The cap is the number of collected items
+impl<T> FromIterator<T> for BoundedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = VecList::new();
+        for val in iter {
+            list.push_back(val);
+        }
+
+        Self {
+            cap: list.len(),
+            list,
+            policy: EvictionPolicy::default(),
+            on_evict: DropEvicted,
+        }
+    }
+}
+
+impl<T, E, S: Storage<T>> IntoIterator for BoundedList<T, E, S> {
+    type Item = T;
+
+    type IntoIter = IntoIter<T, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
+
+/// Oldest first, like `iter`, for any `E`
+impl<'a, T, E, S: Storage<T>> IntoIterator for &'a BoundedList<T, E, S> {
+    type Item = (&'a T, usize);
+
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
         self.list.iter()
     }
 }
+
+/// Oldest first, like `iter_mut`, for any `E`
+impl<'a, T, E, S: Storage<T>> IntoIterator for &'a mut BoundedList<T, E, S> {
+    type Item = (&'a mut T, usize);
+
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.iter_mut()
+    }
+}
+
+/// As `(cap, elements from oldest to newest)`
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, E, S: Storage<T>> serde::Serialize for BoundedList<T, E, S> {
+    fn serialize<R: serde::Serializer>(&self, serializer: R) -> Result<R::Ok, R::Error> {
+        use serde::ser::SerializeTuple;
+
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&self.cap)?;
+        tuple.serialize_element(&self.list)?;
+        tuple.end()
+    }
+}
+
+/// Refills by `add`, so elements beyond `cap` are evicted
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for BoundedList<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (cap, list) = <(usize, VecList<T>)>::deserialize(deserializer)?;
+
+        /* `cap` comes from the input, so the list grows by `add` instead of allocating it */
+        let mut bounded = Self {
+            cap,
+            ..Self::default()
+        };
+        bounded.extend(list);
+        Ok(bounded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vals<T: Clone, E, S: Storage<T>>(list: &BoundedList<T, E, S>) -> Vec<T> {
+        list.to_vec()
+    }
+
+    #[test]
+    fn evict_oldest_wraps_in_order() {
+        let mut list = BoundedList::new(3);
+        for i in 0..3 {
+            assert!(list.add(i).is_some());
+        }
+        assert_eq!(vals(&list), [0, 1, 2]);
+
+        /* the first wrap, and every one after it */
+        for i in 3..20 {
+            list.add(i);
+            assert_eq!(vals(&list), [i - 2, i - 1, i]);
+            assert_eq!(list.front().map(|(x, _)| *x), Some(i - 2));
+            assert_eq!(list.back().map(|(x, _)| *x), Some(i));
+            assert_eq!(list.len(), 3);
+        }
+        assert_eq!(
+            list.iter().rev().map(|(x, _)| *x).collect::<Vec<_>>(),
+            [19, 18, 17]
+        );
+    }
+
+    #[test]
+    fn evict_newest_keeps_the_oldest() {
+        let mut evicted = Vec::new();
+        let mut list =
+            BoundedList::with_on_evict(3, EvictionPolicy::EvictNewest, |x| evicted.push(x));
+        for i in 0..6 {
+            list.add(i);
+        }
+        assert_eq!(vals(&list), [0, 1, 5]);
+        drop(list);
+
+        assert_eq!(evicted, [2, 3, 4]);
+    }
+
+    #[test]
+    fn reject_keeps_the_first_ones() {
+        let mut rejected = Vec::new();
+        let mut list = BoundedList::with_on_evict(2, EvictionPolicy::Reject, |x| rejected.push(x));
+        assert!(list.add(0).is_some());
+        assert!(list.add(1).is_some());
+        assert_eq!(list.add(2), None);
+        assert_eq!(list.add(3), None);
+        assert_eq!(vals(&list), [0, 1]);
+
+        /* room again after a pop, at the back */
+        assert_eq!(list.pop_front(), Some(0));
+        assert!(list.add(4).is_some());
+        assert_eq!(vals(&list), [1, 4]);
+        drop(list);
+
+        assert_eq!(rejected, [2, 3]);
+    }
+
+    #[test]
+    fn try_add_never_evicts() {
+        for policy in [
+            EvictionPolicy::EvictOldest,
+            EvictionPolicy::EvictNewest,
+            EvictionPolicy::Reject,
+        ] {
+            let mut list = BoundedList::with_policy(2, policy);
+            assert!(list.try_add(0).is_ok());
+            assert!(list.try_add(1).is_ok());
+            assert_eq!(list.try_add(2), Err(2));
+            assert_eq!(vals(&list), [0, 1]);
+
+            list.pop_back();
+            assert!(list.try_add(3).is_ok());
+            assert_eq!(vals(&list), [0, 3]);
+        }
+    }
+
+    #[test]
+    fn set_capacity_shrinks_from_the_oldest() {
+        let mut evicted = Vec::new();
+        let mut list =
+            BoundedList::with_on_evict(5, EvictionPolicy::EvictOldest, |x| evicted.push(x));
+        for i in 0..7 {
+            list.add(i);
+        }
+        assert_eq!(vals(&list), [2, 3, 4, 5, 6]);
+
+        list.set_capacity(2);
+        assert_eq!(vals(&list), [5, 6]);
+        assert!(list.is_full());
+
+        list.add(7);
+        assert_eq!(vals(&list), [6, 7]);
+
+        list.set_capacity(4);
+        list.add(8);
+        list.add(9);
+        assert_eq!(vals(&list), [6, 7, 8, 9]);
+        list.add(10);
+        assert_eq!(vals(&list), [7, 8, 9, 10]);
+        drop(list);
+
+        assert_eq!(evicted, [0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn zero_cap_keeps_nothing() {
+        let mut list = BoundedList::new(0);
+        assert_eq!(list.add(1), None);
+        assert!(list.is_empty());
+        assert_eq!(list.try_add(1), Err(1));
+    }
+
+    #[test]
+    fn array_storage_hands_evictions_over() {
+        let mut evicted = Vec::new();
+        let mut list = BoundedList::with_storage_and_on_evict(
+            2,
+            EvictionPolicy::EvictOldest,
+            ArrayStorage::<u32, 2>::new(),
+            |x| evicted.push(x),
+        );
+        for i in 0..5 {
+            list.add(i);
+        }
+        assert_eq!(vals(&list), [3, 4]);
+        assert_eq!(list.as_list().cap(), 2);
+
+        list.set_capacity(1);
+        drop(list);
+        assert_eq!(evicted, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cap is too large for the storage!")]
+    fn storage_and_on_evict_checks_the_cap() {
+        BoundedList::with_storage_and_on_evict(
+            3,
+            EvictionPolicy::default(),
+            ArrayStorage::<u32, 2>::new(),
+            DropEvicted,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cap is too large for the storage!")]
+    fn set_capacity_stays_within_the_storage() {
+        let mut list = ArrayBoundedList::<u32, 2>::default();
+        list.set_capacity(2);
+        list.set_capacity(4);
+    }
+
+    #[test]
+    fn array_bounded_list_wraps_in_place() {
+        let mut list = ArrayBoundedList::<u32, 3>::with_storage(3, ArrayStorage::new());
+        for i in 0..3 {
+            list.add(i);
+        }
+        let slots: Vec<_> = list.iter().map(|(_, idx)| idx).collect();
+
+        for i in 3..10 {
+            list.add(i);
+            assert_eq!(vals(&list), [i - 2, i - 1, i]);
+            /* the freed slot is reused, nothing grows past the 3 slots */
+            assert_eq!(list.as_list().cap(), 3);
+        }
+        let mut reused: Vec<_> = list.iter().map(|(_, idx)| idx).collect();
+        reused.sort_unstable();
+        assert_eq!(reused, slots);
+
+        list.set_policy(EvictionPolicy::EvictNewest);
+        list.add(10);
+        assert_eq!(vals(&list), [7, 8, 10]);
+
+        list.set_policy(EvictionPolicy::Reject);
+        assert_eq!(list.add(11), None);
+        assert_eq!(list.try_add(11), Err(11));
+
+        list.set_capacity(1);
+        assert_eq!(vals(&list), [10]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trips_cap_and_order() {
+        let mut list = BoundedList::new(3);
+        list.extend(0..5);
+
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(json, "[3,[2,3,4]]");
+        let back: BoundedList<u32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.capacity(), 3);
+        assert_eq!(vals(&back), [2, 3, 4]);
+
+        /* more elements than the cap are evicted oldest first */
+        let back: BoundedList<u32> = serde_json::from_str("[2,[1,2,3]]").unwrap();
+        assert_eq!(vals(&back), [2, 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_doesnt_allocate_the_cap() {
+        let back: BoundedList<u32> =
+            serde_json::from_str("[18446744073709551615, [1, 2]]").unwrap();
+        assert_eq!(back.capacity(), usize::MAX);
+        assert_eq!(vals(&back), [1, 2]);
+        assert!(back.as_list().vec_cap() < 4096);
+    }
+}
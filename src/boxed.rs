@@ -0,0 +1,30 @@
+use crate::VecList;
+
+/// A `VecList` of boxed values for a large `T`: the slots hold pointers, so growing,
+/// `optimize_for_iteration` and `delete_fill` move 8 bytes per element instead of a `T`,
+/// and a value stays at the same address as long as it's in the list.
+///
+/// A `clone` still clones every `T`.
+pub type BoxedVecList<T> = VecList<Box<T>>;
+
+impl<T> VecList<Box<T>> {
+    /// Average O(1), boxes `val`
+    pub fn push_back_boxed(&mut self, val: T) -> usize {
+        self.push_back(Box::new(val))
+    }
+
+    /// Average O(1), boxes `val`
+    pub fn push_front_boxed(&mut self, val: T) -> usize {
+        self.push_front(Box::new(val))
+    }
+
+    /// O(1), unboxes the deleted value
+    pub fn delete_unboxed(&mut self, idx: usize) -> Option<T> {
+        self.delete(idx).map(|val| *val)
+    }
+
+    /// O(n), the values in order, unboxed, with new indices `0..len`
+    pub fn into_unboxed(self) -> VecList<T> {
+        self.into_iter().map(|val| *val).collect()
+    }
+}
@@ -0,0 +1,148 @@
+use std::marker::PhantomData;
+use std::ops;
+
+use crate::Storage;
+use crate::VecList;
+use crate::VecStorage;
+
+/* invariant in 'id, so two brands never unify */
+type Brand<'id> = PhantomData<fn(&'id ()) -> &'id ()>;
+
+/// An index which can only be used with the `BrandedList` that created it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BrandedIndex<'id> {
+    idx: usize,
+    brand: Brand<'id>,
+}
+
+impl<'id> BrandedIndex<'id> {
+    /// The raw slot index
+    pub fn index(self) -> usize {
+        self.idx
+    }
+}
+
+/// A `VecList` whose indices are branded with `'id`, created by `VecList::with_brand`.
+///
+/// Using an index of another list is a compile error:
+///
+/// ```compile_fail
+/// use vec_list::VecList;
+///
+/// let mut a = VecList::new();
+/// let mut b: VecList<i32> = VecList::new();
+/// a.with_brand(|mut a| {
+///     b.with_brand(|b| {
+///         let idx = a.push_back(1);
+///         b.get(idx);
+///     })
+/// });
+/// ```
+pub struct BrandedList<'a, 'id, T, S: Storage<T> = VecStorage<T>> {
+    list: &'a mut VecList<T, S>,
+    brand: Brand<'id>,
+}
+
+impl<T, S: Storage<T>> VecList<T, S> {
+    /// Runs `f` with a branded view of the list
+    pub fn with_brand<R, F>(&mut self, f: F) -> R
+    where
+        F: for<'id> FnOnce(BrandedList<'_, 'id, T, S>) -> R,
+    {
+        f(BrandedList {
+            list: self,
+            brand: PhantomData,
+        })
+    }
+}
+
+impl<'a, 'id, T, S: Storage<T>> BrandedList<'a, 'id, T, S> {
+    fn brand(&self, idx: usize) -> BrandedIndex<'id> {
+        BrandedIndex {
+            idx,
+            brand: PhantomData,
+        }
+    }
+
+    /// Average O(1)
+    pub fn push_back(&mut self, val: T) -> BrandedIndex<'id> {
+        let idx = self.list.push_back(val);
+        self.brand(idx)
+    }
+
+    /// Average O(1)
+    pub fn push_front(&mut self, val: T) -> BrandedIndex<'id> {
+        let idx = self.list.push_front(val);
+        self.brand(idx)
+    }
+
+    /// O(1)
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    /// O(1)
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+
+    /// O(1)
+    pub fn front(&self) -> Option<(&T, BrandedIndex<'id>)> {
+        self.list.front().map(|(val, idx)| (val, self.brand(idx)))
+    }
+
+    /// O(1)
+    pub fn back(&self) -> Option<(&T, BrandedIndex<'id>)> {
+        self.list.back().map(|(val, idx)| (val, self.brand(idx)))
+    }
+
+    /// O(1), `None` if it's deleted
+    pub fn delete(&mut self, idx: BrandedIndex<'id>) -> Option<T> {
+        self.list.delete(idx.idx)
+    }
+
+    /// `None` if it's deleted
+    pub fn get(&self, idx: BrandedIndex<'id>) -> Option<&T> {
+        self.list.get(idx.idx)
+    }
+
+    /// `None` if it's deleted
+    pub fn get_mut(&mut self, idx: BrandedIndex<'id>) -> Option<&mut T> {
+        self.list.get_mut(idx.idx)
+    }
+
+    pub fn next(&self, idx: BrandedIndex<'id>) -> Option<BrandedIndex<'id>> {
+        self.list.next(idx.idx).map(|idx| self.brand(idx))
+    }
+
+    pub fn previous(&self, idx: BrandedIndex<'id>) -> Option<BrandedIndex<'id>> {
+        self.list.previous(idx.idx).map(|idx| self.brand(idx))
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Read-only, the raw indices it gives are unbranded
+    pub fn as_list(&self) -> &VecList<T, S> {
+        self.list
+    }
+}
+
+impl<'a, 'id, T, S: Storage<T>> ops::Index<BrandedIndex<'id>> for BrandedList<'a, 'id, T, S> {
+    type Output = T;
+
+    fn index(&self, index: BrandedIndex<'id>) -> &Self::Output {
+        self.get(index).expect("invalid key!")
+    }
+}
+
+impl<'a, 'id, T, S: Storage<T>> ops::IndexMut<BrandedIndex<'id>> for BrandedList<'a, 'id, T, S> {
+    fn index_mut(&mut self, index: BrandedIndex<'id>) -> &mut Self::Output {
+        self.get_mut(index).expect("invalid key!")
+    }
+}
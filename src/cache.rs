@@ -0,0 +1,544 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::mem;
+
+use crate::Values;
+use crate::VecList;
+
+/// Decides which entry a `Cache` evicts, entries are known by their slot indices.
+///
+/// A policy only sees indices which are in the cache, and each index is handed to `on_insert`
+/// again after it's evicted or removed and then reused.
+pub trait CachePolicy {
+    /// `idx` is a new entry
+    fn on_insert(&mut self, idx: usize);
+
+    /// `idx` is read or overwritten
+    fn on_access(&mut self, idx: usize);
+
+    /// `idx` is removed by hand
+    fn on_remove(&mut self, idx: usize);
+
+    /// All entries are removed
+    fn on_clear(&mut self);
+
+    /// Called when the cache is full, the policy forgets the returned index,
+    /// which must be in the cache
+    fn select_victim(&mut self) -> Option<usize>;
+}
+
+/* cache slot indices in some order, each one knows its node */
+#[derive(Debug, Default, Clone)]
+struct Order {
+    list: VecList<usize>,
+    nodes: Vec<usize>,
+}
+
+impl Order {
+    fn push_back(&mut self, idx: usize) {
+        if idx >= self.nodes.len() {
+            self.nodes.resize(idx + 1, 0);
+        }
+        self.nodes[idx] = self.list.push_back(idx);
+    }
+
+    fn remove(&mut self, idx: usize) {
+        self.list.delete(self.nodes[idx]);
+    }
+
+    fn clear(&mut self) {
+        self.list.clear();
+        self.nodes.clear();
+    }
+}
+
+/// Evicts the least recently used
+#[derive(Debug, Default, Clone)]
+pub struct Lru {
+    order: Order,
+}
+
+impl CachePolicy for Lru {
+    fn on_insert(&mut self, idx: usize) {
+        self.order.push_back(idx);
+    }
+
+    fn on_access(&mut self, idx: usize) {
+        self.order.remove(idx);
+        self.order.push_back(idx);
+    }
+
+    fn on_remove(&mut self, idx: usize) {
+        self.order.remove(idx);
+    }
+
+    fn on_clear(&mut self) {
+        self.order.clear();
+    }
+
+    fn select_victim(&mut self) -> Option<usize> {
+        self.order.list.pop_front()
+    }
+}
+
+/// Evicts the oldest inserted, reads don't matter
+#[derive(Debug, Default, Clone)]
+pub struct Fifo {
+    order: Order,
+}
+
+impl CachePolicy for Fifo {
+    fn on_insert(&mut self, idx: usize) {
+        self.order.push_back(idx);
+    }
+
+    fn on_access(&mut self, _: usize) {}
+
+    fn on_remove(&mut self, idx: usize) {
+        self.order.remove(idx);
+    }
+
+    fn on_clear(&mut self) {
+        self.order.clear();
+    }
+
+    fn select_victim(&mut self) -> Option<usize> {
+        self.order.list.pop_front()
+    }
+}
+
+/// Counters of a `Cache` since it was made or since `Cache::reset_stats`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CacheStats {
+    /// `get`s and `get_mut`s which found the key
+    pub hits: u64,
+    /// `get`s and `get_mut`s which didn't
+    pub misses: u64,
+    /// Entries dropped by the policy to make room
+    pub evictions: u64,
+}
+
+impl CacheStats {
+    /// `hits / (hits + misses)`, `None` before any lookup
+    pub fn hit_rate(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
+}
+
+/// A map holding at most `cap` entries, the `CachePolicy` picks which one goes when it's full
+#[derive(Debug, Clone)]
+pub struct Cache<K, V, P = Lru> {
+    /* in insertion order */
+    entries: VecList<(K, V)>,
+    map: HashMap<K, usize>,
+    cap: usize,
+    policy: P,
+    stats: CacheStats,
+}
+
+pub type LruCache<K, V> = Cache<K, V, Lru>;
+
+/// A key's place in a `Cache`, from `Cache::entry`
+pub enum CacheEntry<'a, K, V, P> {
+    Occupied(OccupiedCacheEntry<'a, K, V, P>),
+    Vacant(VacantCacheEntry<'a, K, V, P>),
+}
+
+/// A key which is in the cache
+pub struct OccupiedCacheEntry<'a, K, V, P> {
+    cache: &'a mut Cache<K, V, P>,
+    idx: usize,
+}
+
+/// A key which is not in the cache
+pub struct VacantCacheEntry<'a, K, V, P> {
+    cache: &'a mut Cache<K, V, P>,
+    key: K,
+}
+
+impl<K: Hash + Eq + Clone, V> Cache<K, V> {
+    /// Panics if `cap` is 0
+    pub fn new(cap: usize) -> Self {
+        Self::with_policy(cap, Lru::default())
+    }
+}
+
+impl<K: Hash + Eq + Clone, V, P: CachePolicy> Cache<K, V, P> {
+    /// Panics if `cap` is 0
+    pub fn with_policy(cap: usize, policy: P) -> Self {
+        assert!(cap > 0, "cache has no room!");
+
+        Self {
+            entries: VecList::with_capacity(cap),
+            map: HashMap::with_capacity(cap),
+            cap,
+            policy,
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Average O(1), gives back the old value of `key`, evicts one entry if it's new and the cache is full
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        if let Some(&idx) = self.map.get(&key) {
+            self.policy.on_access(idx);
+            return Some(mem::replace(&mut self.entries[idx].1, val));
+        }
+
+        self.insert_new(key, val);
+        None
+    }
+
+    /// Average O(1), counts as an access and a hit or miss like `get`
+    pub fn entry(&mut self, key: K) -> CacheEntry<'_, K, V, P> {
+        match self.map.get(&key) {
+            Some(&idx) => {
+                self.stats.hits += 1;
+                self.policy.on_access(idx);
+                CacheEntry::Occupied(OccupiedCacheEntry { cache: self, idx })
+            }
+            None => {
+                self.stats.misses += 1;
+                CacheEntry::Vacant(VacantCacheEntry { cache: self, key })
+            }
+        }
+    }
+
+    /// Average O(1), `entry(key).or_insert_with(f)`, so `f` only runs on a miss
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> &mut V {
+        self.entry(key).or_insert_with(f)
+    }
+
+    /// Average O(1), counts as an access
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(&idx) = self.map.get(key) else {
+            self.stats.misses += 1;
+            return None;
+        };
+        self.stats.hits += 1;
+        self.policy.on_access(idx);
+        self.entries.get(idx).map(|(_, val)| val)
+    }
+
+    /// Average O(1), counts as an access
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(&idx) = self.map.get(key) else {
+            self.stats.misses += 1;
+            return None;
+        };
+        self.stats.hits += 1;
+        self.policy.on_access(idx);
+        self.entries.get_mut(idx).map(|(_, val)| val)
+    }
+
+    /// Average O(1), doesn't count as an access
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = *self.map.get(key)?;
+        self.entries.get(idx).map(|(_, val)| val)
+    }
+
+    /// Average O(1), doesn't count as an access
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Average O(1)
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.map.remove(key)?;
+        self.policy.on_remove(idx);
+        self.entries.delete(idx).map(|(_, val)| val)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.map.clear();
+        self.policy.on_clear();
+    }
+
+    /// In insertion order, nothing counts as an access
+    pub fn iter(&self) -> Values<'_, (K, V)> {
+        self.entries.values()
+    }
+
+    pub fn policy(&self) -> &P {
+        &self.policy
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Zeroes the counters, `clear` keeps them
+    pub fn reset_stats(&mut self) {
+        self.stats = CacheStats::default();
+    }
+
+    /// `key` must not be in the cache, evicts one entry if it's full
+    fn insert_new(&mut self, key: K, val: V) -> usize {
+        if self.len() == self.cap {
+            if let Some(victim) = self.policy.select_victim() {
+                let (evicted, _) = self
+                    .entries
+                    .delete(victim)
+                    .expect("victim is not in the cache!");
+                self.map.remove(&evicted);
+                self.stats.evictions += 1;
+            }
+        }
+
+        let idx = self.entries.push_back((key.clone(), val));
+        self.map.insert(key, idx);
+        self.policy.on_insert(idx);
+        idx
+    }
+}
+
+impl<'a, K: Hash + Eq + Clone, V, P: CachePolicy> CacheEntry<'a, K, V, P> {
+    pub fn key(&self) -> &K {
+        match self {
+            Self::Occupied(entry) => entry.key(),
+            Self::Vacant(entry) => entry.key(),
+        }
+    }
+
+    pub fn or_insert(self, val: V) -> &'a mut V {
+        self.or_insert_with(|| val)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(self, f: F) -> &'a mut V {
+        match self {
+            Self::Occupied(entry) => entry.into_mut(),
+            Self::Vacant(entry) => entry.insert(f()),
+        }
+    }
+
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Self::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K: Hash + Eq + Clone, V, P: CachePolicy> OccupiedCacheEntry<'a, K, V, P> {
+    pub fn key(&self) -> &K {
+        &self.cache.entries[self.idx].0
+    }
+
+    pub fn get(&self) -> &V {
+        &self.cache.entries[self.idx].1
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.cache.entries[self.idx].1
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.cache.entries[self.idx].1
+    }
+
+    /// Gives back the old value
+    pub fn insert(&mut self, val: V) -> V {
+        mem::replace(self.get_mut(), val)
+    }
+
+    /// Average O(1)
+    pub fn remove(self) -> V {
+        let (key, val) = self
+            .cache
+            .entries
+            .delete(self.idx)
+            .expect("entry is not in the cache!");
+        self.cache.map.remove(&key);
+        self.cache.policy.on_remove(self.idx);
+        val
+    }
+}
+
+impl<'a, K: Hash + Eq + Clone, V, P: CachePolicy> VacantCacheEntry<'a, K, V, P> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Average O(1), evicts one entry if the cache is full
+    pub fn insert(self, val: V) -> &'a mut V {
+        let idx = self.cache.insert_new(self.key, val);
+        &mut self.cache.entries[idx].1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "cache has no room!")]
+    fn zero_cap_is_rejected() {
+        Cache::<u32, u32>::new(0);
+    }
+
+    #[test]
+    fn every_insert_evicts_alike() {
+        let mut cache = Cache::new(1);
+        assert_eq!(cache.insert(1, 10), None);
+        assert_eq!(*cache.get_or_insert_with(2, || 20), 20);
+        assert_eq!(cache.peek(&1), None);
+        match cache.entry(3) {
+            CacheEntry::Vacant(entry) => *entry.insert(30) += 1,
+            CacheEntry::Occupied(_) => unreachable!(),
+        }
+        assert_eq!(cache.peek(&2), None);
+        assert_eq!(cache.peek(&3), Some(&31));
+        assert_eq!(cache.stats().evictions, 2);
+    }
+
+    fn keys<P: CachePolicy>(cache: &Cache<char, u32, P>) -> String {
+        cache.iter().map(|(key, _)| key).collect()
+    }
+
+    #[test]
+    fn lru_evicts_the_least_recently_used() {
+        let mut cache = Cache::new(3);
+        for (i, key) in "abc".chars().enumerate() {
+            cache.insert(key, i as u32);
+        }
+
+        cache.get(&'a');
+        cache.insert('d', 3);
+        assert!(!cache.contains_key(&'b'));
+
+        /* `get_mut` and overwriting are accesses, `peek` is not */
+        cache.get_mut(&'c');
+        assert_eq!(cache.insert('a', 10), Some(0));
+        cache.peek(&'d');
+        cache.insert('e', 4);
+        assert!(!cache.contains_key(&'d'));
+        cache.insert('f', 5);
+        assert!(!cache.contains_key(&'c'));
+        assert_eq!(keys(&cache), "aef");
+    }
+
+    #[test]
+    fn fifo_ignores_accesses() {
+        let mut cache = Cache::with_policy(3, Fifo::default());
+        for (i, key) in "abc".chars().enumerate() {
+            cache.insert(key, i as u32);
+        }
+
+        cache.get(&'a');
+        cache.insert('a', 10);
+        cache.insert('d', 3);
+        assert!(!cache.contains_key(&'a'));
+        cache.insert('e', 4);
+        assert!(!cache.contains_key(&'b'));
+
+        /* a removed one is out of the order too */
+        assert_eq!(cache.remove(&'c'), Some(2));
+        cache.insert('f', 5);
+        cache.insert('g', 6);
+        assert_eq!(keys(&cache), "efg");
+    }
+
+    #[test]
+    fn entry_upserts() {
+        let mut cache = Cache::new(4);
+        for key in "abab".chars() {
+            *cache.entry(key).or_insert(0) += 1;
+        }
+        assert_eq!((cache.peek(&'a'), cache.peek(&'b')), (Some(&2), Some(&2)));
+
+        cache.entry('a').and_modify(|val| *val *= 10).or_insert(1);
+        cache.entry('c').and_modify(|val| *val *= 10).or_insert(1);
+        assert_eq!((cache.peek(&'a'), cache.peek(&'c')), (Some(&20), Some(&1)));
+
+        match cache.entry('b') {
+            CacheEntry::Occupied(mut entry) => {
+                assert_eq!(entry.key(), &'b');
+                assert_eq!(entry.insert(7), 2);
+                assert_eq!(entry.remove(), 7);
+            }
+            CacheEntry::Vacant(_) => unreachable!(),
+        }
+        assert!(!cache.contains_key(&'b'));
+        match cache.entry('z') {
+            CacheEntry::Vacant(entry) => assert_eq!(entry.into_key(), 'z'),
+            CacheEntry::Occupied(_) => unreachable!(),
+        }
+        assert!(!cache.contains_key(&'z'));
+
+        /* `f` only runs on a miss */
+        assert_eq!(*cache.get_or_insert_with('a', || unreachable!()), 20);
+        assert_eq!(*cache.get_or_insert_with('d', || 4), 4);
+        assert_eq!(keys(&cache), "acd");
+    }
+
+    #[test]
+    fn stats_count_every_path() {
+        let mut cache = Cache::new(2);
+        assert_eq!(cache.stats().hit_rate(), None);
+
+        cache.insert('a', 0);
+        assert_eq!(cache.get(&'a'), Some(&0));
+        assert_eq!(cache.get_mut(&'b'), None);
+        assert!(matches!(cache.entry('a'), CacheEntry::Occupied(_)));
+        cache.entry('b').or_insert(1);
+        cache.get_or_insert_with('b', || 1);
+        cache.get_or_insert_with('c', || 2);
+        /* neither lookups nor accesses */
+        cache.peek(&'x');
+        cache.contains_key(&'x');
+        cache.insert('b', 5);
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (3, 3, 1));
+        assert_eq!(stats.hit_rate(), Some(0.5));
+
+        /* every way in evicts when full, removing by hand is no eviction */
+        cache.insert('d', 3);
+        cache.entry('e').or_insert(4);
+        cache.remove(&'e');
+        assert_eq!(cache.stats().evictions, 3);
+
+        cache.clear();
+        assert_eq!(cache.stats().evictions, 3);
+        cache.reset_stats();
+        assert_eq!(cache.stats(), CacheStats::default());
+        assert_eq!(cache.stats().hit_rate(), None);
+    }
+}
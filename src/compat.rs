@@ -0,0 +1,4 @@
+/// The API of the `dlv-list` crate
+pub mod dlv_list;
+/// The API of the `index_list` crate
+pub mod index_list;
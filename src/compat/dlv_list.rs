@@ -0,0 +1,272 @@
+use core::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::marker::PhantomData;
+
+use crate::Drain;
+use crate::IntoIter;
+use crate::Iter;
+use crate::Values;
+use crate::ValuesMut;
+
+/// `dlv_list::VecList` on top of `crate::VecList`, so switching is mostly a `use` change.
+///
+/// Unlike in `dlv-list`, an `Index` has no generation, so a stale one may point at
+/// the element which reused its slot instead of giving `None`.
+#[derive(Debug, Default, Clone)]
+pub struct VecList<T> {
+    list: crate::VecList<T>,
+}
+
+/// A handle of an element, the slot index typed by the element type
+pub struct Index<T> {
+    idx: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// The indices front to back, from `VecList::indices`
+pub struct Indices<'a, T> {
+    iter: Iter<'a, T>,
+}
+
+impl<T> Index<T> {
+    fn new(idx: usize) -> Self {
+        Self {
+            idx,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The index in `crate::VecList`
+    pub fn slot(self) -> usize {
+        self.idx
+    }
+}
+
+impl<T> VecList<T> {
+    pub const fn new() -> Self {
+        Self {
+            list: crate::VecList::new(),
+        }
+    }
+
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            list: crate::VecList::with_capacity(cap),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.list.vec_cap()
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+
+    /// Average O(1)
+    pub fn push_back(&mut self, value: T) -> Index<T> {
+        Index::new(self.list.push_back(value))
+    }
+
+    /// Average O(1)
+    pub fn push_front(&mut self, value: T) -> Index<T> {
+        Index::new(self.list.push_front(value))
+    }
+
+    /// O(1)
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+
+    /// O(1)
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.list.front().map(|(val, _)| val)
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.list.front_mut().map(|(val, _)| val)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.list.back().map(|(val, _)| val)
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.list.back_mut().map(|(val, _)| val)
+    }
+
+    pub fn front_index(&self) -> Option<Index<T>> {
+        self.list.front().map(|(_, idx)| Index::new(idx))
+    }
+
+    pub fn back_index(&self) -> Option<Index<T>> {
+        self.list.back().map(|(_, idx)| Index::new(idx))
+    }
+
+    pub fn get(&self, index: Index<T>) -> Option<&T> {
+        self.list.get(index.idx)
+    }
+
+    pub fn get_mut(&mut self, index: Index<T>) -> Option<&mut T> {
+        self.list.get_mut(index.idx)
+    }
+
+    pub fn get_next_index(&self, index: Index<T>) -> Option<Index<T>> {
+        self.list.next(index.idx).map(Index::new)
+    }
+
+    pub fn get_previous_index(&self, index: Index<T>) -> Option<Index<T>> {
+        self.list.previous(index.idx).map(Index::new)
+    }
+
+    /// Average O(1), panics if `index` is removed
+    pub fn insert_before(&mut self, index: Index<T>, value: T) -> Index<T> {
+        Index::new(self.list.insert_before(index.idx, value))
+    }
+
+    /// Average O(1), panics if `index` is removed
+    pub fn insert_after(&mut self, index: Index<T>, value: T) -> Index<T> {
+        Index::new(self.list.insert_after(index.idx, value))
+    }
+
+    /// O(1)
+    pub fn remove(&mut self, index: Index<T>) -> Option<T> {
+        self.list.delete(index.idx)
+    }
+
+    pub fn iter(&self) -> Values<'_, T> {
+        self.list.values()
+    }
+
+    pub fn iter_mut(&mut self) -> ValuesMut<'_, T> {
+        self.list.values_mut()
+    }
+
+    pub fn indices(&self) -> Indices<'_, T> {
+        Indices {
+            iter: self.list.iter(),
+        }
+    }
+
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        self.list.drain()
+    }
+
+    /// O(n), `keep` sees the values front to back
+    pub fn retain<F: FnMut(&mut T) -> bool>(&mut self, mut keep: F) {
+        self.list.retain_indexed(|_, val| keep(val));
+    }
+
+    pub fn into_inner(self) -> crate::VecList<T> {
+        self.list
+    }
+}
+
+impl<T: PartialEq> VecList<T> {
+    /// O(n)
+    pub fn contains(&self, value: &T) -> bool {
+        self.list.contains(value)
+    }
+}
+
+impl<T> From<crate::VecList<T>> for VecList<T> {
+    fn from(list: crate::VecList<T>) -> Self {
+        Self { list }
+    }
+}
+
+impl<T> FromIterator<T> for VecList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            list: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<T> for VecList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.list.extend(iter);
+    }
+}
+
+impl<T> IntoIterator for VecList<T> {
+    type Item = T;
+
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a VecList<T> {
+    type Item = &'a T;
+
+    type IntoIter = Values<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> Iterator for Indices<'a, T> {
+    type Item = Index<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, idx)| Index::new(idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Indices<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(_, idx)| Index::new(idx))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Indices<'a, T> {}
+
+/* by hand, so `T` needs none of these */
+impl<T> Clone for Index<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Index<T> {}
+
+impl<T> PartialEq for Index<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.idx == other.idx
+    }
+}
+
+impl<T> Eq for Index<T> {}
+
+impl<T> Hash for Index<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.idx.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Index<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Index").field(&self.idx).finish()
+    }
+}
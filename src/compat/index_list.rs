@@ -0,0 +1,239 @@
+use crate::Drain;
+use crate::IntoIter;
+use crate::Values;
+use crate::ValuesMut;
+use crate::VecList;
+
+/// `index_list::IndexList` on top of `VecList`, so switching is mostly a `use` change.
+///
+/// Like in `index_list`, a removed element's index is reused by later inserts.
+#[derive(Debug, Default, Clone)]
+pub struct IndexList<T> {
+    list: VecList<T>,
+}
+
+/// A handle of an element, or none, which is what the `*_index` walks give past the ends
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ListIndex {
+    idx: Option<usize>,
+}
+
+impl ListIndex {
+    /// The none index
+    pub const fn new() -> Self {
+        Self { idx: None }
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.idx.is_none()
+    }
+
+    pub fn is_some(&self) -> bool {
+        self.idx.is_some()
+    }
+
+    /// The index in `VecList`
+    pub fn slot(&self) -> Option<usize> {
+        self.idx
+    }
+}
+
+impl From<Option<usize>> for ListIndex {
+    fn from(idx: Option<usize>) -> Self {
+        Self { idx }
+    }
+}
+
+impl From<usize> for ListIndex {
+    fn from(idx: usize) -> Self {
+        Self { idx: Some(idx) }
+    }
+}
+
+impl<T> IndexList<T> {
+    pub const fn new() -> Self {
+        Self {
+            list: VecList::new(),
+        }
+    }
+
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            list: VecList::with_capacity(cap),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.list.vec_cap()
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+
+    /// O(1), whether `index` holds an element
+    pub fn is_index_used(&self, index: ListIndex) -> bool {
+        self.get(index).is_some()
+    }
+
+    pub fn first_index(&self) -> ListIndex {
+        self.list.front().map(|(_, idx)| idx).into()
+    }
+
+    pub fn last_index(&self) -> ListIndex {
+        self.list.back().map(|(_, idx)| idx).into()
+    }
+
+    /// None after the last one, or for a none or unused `index`
+    pub fn next_index(&self, index: ListIndex) -> ListIndex {
+        index.idx.and_then(|idx| self.list.next(idx)).into()
+    }
+
+    /// None before the first one, or for a none or unused `index`
+    pub fn prev_index(&self, index: ListIndex) -> ListIndex {
+        index.idx.and_then(|idx| self.list.previous(idx)).into()
+    }
+
+    pub fn get(&self, index: ListIndex) -> Option<&T> {
+        self.list.get(index.idx?)
+    }
+
+    pub fn get_mut(&mut self, index: ListIndex) -> Option<&mut T> {
+        self.list.get_mut(index.idx?)
+    }
+
+    pub fn get_first(&self) -> Option<&T> {
+        self.list.front().map(|(val, _)| val)
+    }
+
+    pub fn get_last(&self) -> Option<&T> {
+        self.list.back().map(|(val, _)| val)
+    }
+
+    pub fn get_mut_first(&mut self) -> Option<&mut T> {
+        self.list.front_mut().map(|(val, _)| val)
+    }
+
+    pub fn get_mut_last(&mut self) -> Option<&mut T> {
+        self.list.back_mut().map(|(val, _)| val)
+    }
+
+    /// Average O(1)
+    pub fn insert_first(&mut self, elem: T) -> ListIndex {
+        self.list.push_front(elem).into()
+    }
+
+    /// Average O(1)
+    pub fn insert_last(&mut self, elem: T) -> ListIndex {
+        self.list.push_back(elem).into()
+    }
+
+    /// Average O(1), a none `index` stands for past the last one, so it's `insert_last`.
+    /// Panics if `index` is unused.
+    pub fn insert_before(&mut self, index: ListIndex, elem: T) -> ListIndex {
+        match index.idx {
+            Some(idx) => self.list.insert_before(idx, elem).into(),
+            None => self.insert_last(elem),
+        }
+    }
+
+    /// Average O(1), a none `index` stands for before the first one, so it's `insert_first`.
+    /// Panics if `index` is unused.
+    pub fn insert_after(&mut self, index: ListIndex, elem: T) -> ListIndex {
+        match index.idx {
+            Some(idx) => self.list.insert_after(idx, elem).into(),
+            None => self.insert_first(elem),
+        }
+    }
+
+    /// O(1)
+    pub fn remove(&mut self, index: ListIndex) -> Option<T> {
+        self.list.delete(index.idx?)
+    }
+
+    /// O(1)
+    pub fn remove_first(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    /// O(1)
+    pub fn remove_last(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+
+    pub fn iter(&self) -> Values<'_, T> {
+        self.list.values()
+    }
+
+    pub fn iter_mut(&mut self) -> ValuesMut<'_, T> {
+        self.list.values_mut()
+    }
+
+    pub fn drain_iter(&mut self) -> Drain<'_, T> {
+        self.list.drain()
+    }
+
+    pub fn into_inner(self) -> VecList<T> {
+        self.list
+    }
+}
+
+impl<T: PartialEq> IndexList<T> {
+    /// O(n)
+    pub fn contains(&self, elem: &T) -> bool {
+        self.list.contains(elem)
+    }
+
+    /// O(n), the first one equal to `elem`, or none
+    pub fn index_of(&self, elem: &T) -> ListIndex {
+        self.list.index_of(elem).into()
+    }
+}
+
+impl<T> From<VecList<T>> for IndexList<T> {
+    fn from(list: VecList<T>) -> Self {
+        Self { list }
+    }
+}
+
+impl<T> FromIterator<T> for IndexList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            list: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<T> for IndexList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.list.extend(iter);
+    }
+}
+
+impl<T> IntoIterator for IndexList<T> {
+    type Item = T;
+
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IndexList<T> {
+    type Item = &'a T;
+
+    type IntoIter = Values<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,430 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+
+use crate::Link;
+use crate::Storage;
+use crate::VecList;
+use crate::VecStorage;
+
+/// A cursor which can edit the list, like `LinkedList`'s.
+///
+/// Besides the elements there is a "ghost" position, moving past either end lands on it,
+/// and moving from it lands on the other end.
+pub struct CursorMut<'a, T, S: Storage<T> = VecStorage<T>> {
+    list: &'a mut VecList<T, S>,
+    /* `None` is the ghost */
+    cur: Option<usize>,
+}
+
+/// One of the two cursors from `VecList::twin_cursors_mut`, it never moves onto the element
+/// the other one is on, so both can hand out `&mut T` at the same time.
+/// They can't add or remove elements.
+pub struct TwinCursorMut<'a, T> {
+    links: &'a [Link],
+    /* raw, like `IterMut` */
+    vals: *mut MaybeUninit<T>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    cur: &'a Cell<Option<usize>>,
+    other: &'a Cell<Option<usize>>,
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<T, S: Storage<T>> VecList<T, S> {
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T, S> {
+        CursorMut {
+            cur: self.head,
+            list: self,
+        }
+    }
+
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T, S> {
+        CursorMut {
+            cur: self.tail,
+            list: self,
+        }
+    }
+
+    /// `None` if `idx` is deleted
+    pub fn cursor_mut(&mut self, idx: usize) -> Option<CursorMut<'_, T, S>> {
+        self.get(idx)?;
+
+        Some(CursorMut {
+            cur: Some(idx),
+            list: self,
+        })
+    }
+
+    /// Runs `f` with a cursor on the front and a cursor on the back,
+    /// the back one starts on the ghost if there is only one element.
+    ///
+    /// ```
+    /// use vec_list::VecList;
+    ///
+    /// let mut list: VecList<u32> = (0..4).collect();
+    /// /* reverses it in place, they stop before running into each other */
+    /// list.twin_cursors_mut(|mut front, mut back| loop {
+    ///     std::mem::swap(front.current_mut().unwrap(), back.current_mut().unwrap());
+    ///     if !front.move_next() || !back.move_prev() {
+    ///         break;
+    ///     }
+    /// });
+    /// assert!(list.values().eq(&[3, 2, 1, 0]));
+    /// ```
+    ///
+    /// The cursors can't leave `f`, so they can't outlive the borrow of the list:
+    ///
+    /// ```compile_fail
+    /// use vec_list::VecList;
+    ///
+    /// let mut list: VecList<u32> = (0..4).collect();
+    /// let front = list.twin_cursors_mut(|front, _| front);
+    /// ```
+    pub fn twin_cursors_mut<R, F>(&mut self, f: F) -> R
+    where
+        F: for<'c> FnOnce(TwinCursorMut<'c, T>, TwinCursorMut<'c, T>) -> R,
+    {
+        let (head, tail) = (self.head, self.tail);
+        let (links, vals) = self.storage.parts_mut();
+        let (links, vals) = (&*links, vals.as_mut_ptr());
+
+        let front = Cell::new(head);
+        let back = Cell::new(if tail == head { None } else { tail });
+        let twin = |cur, other| TwinCursorMut {
+            links,
+            vals,
+            head,
+            tail,
+            cur,
+            other,
+            _marker: PhantomData,
+        };
+
+        f(twin(&front, &back), twin(&back, &front))
+    }
+}
+
+impl<'a, T, S: Storage<T>> CursorMut<'a, T, S> {
+    /// `None` on the ghost
+    pub fn index(&self) -> Option<usize> {
+        self.cur
+    }
+
+    /// `None` on the ghost
+    pub fn current(&mut self) -> Option<&mut T> {
+        Some(unsafe { self.list.val_mut(self.cur?) })
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = self.next_index()?;
+        Some(unsafe { self.list.val_mut(next) })
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = self.prev_index()?;
+        Some(unsafe { self.list.val_mut(prev) })
+    }
+
+    /// O(1)
+    pub fn move_next(&mut self) {
+        self.cur = self.next_index();
+    }
+
+    /// O(1)
+    pub fn move_prev(&mut self) {
+        self.cur = self.prev_index();
+    }
+
+    /// Average O(1), the cursor stays, on the ghost it's a `push_front`
+    pub fn insert_after(&mut self, val: T) -> usize {
+        match self.cur {
+            Some(cur) => self.list.insert_after(cur, val),
+            None => self.list.push_front(val),
+        }
+    }
+
+    /// Average O(1), the cursor stays, on the ghost it's a `push_back`
+    pub fn insert_before(&mut self, val: T) -> usize {
+        match self.cur {
+            Some(cur) => self.list.insert_before(cur, val),
+            None => self.list.push_back(val),
+        }
+    }
+
+    /// O(1), then the cursor is on the next one
+    pub fn remove_current(&mut self) -> Option<T> {
+        let cur = self.cur?;
+        self.cur = self.list.next(cur);
+        self.list.delete(cur)
+    }
+
+    /// O(k), moves the elements after the cursor into a new list, which indexes them anew.
+    /// On the ghost it's all of them.
+    pub fn split_after(&mut self) -> VecList<T, S> {
+        match self.next_index() {
+            Some(from) => self
+                .list
+                .cut_range(from, self.list.tail.expect("invalid key!")),
+            None => VecList::default(),
+        }
+    }
+
+    /// O(k), moves the elements before the cursor into a new list, which indexes them anew.
+    /// On the ghost it's all of them.
+    pub fn split_before(&mut self) -> VecList<T, S> {
+        match self.prev_index() {
+            Some(to) => self
+                .list
+                .cut_range(self.list.head.expect("invalid key!"), to),
+            None => VecList::default(),
+        }
+    }
+
+    /// O(k), moves the elements of `other` in right after the cursor, keeping their order,
+    /// they get new indices. On the ghost they go to the front.
+    pub fn splice_after<O: Storage<T>>(&mut self, other: VecList<T, O>) {
+        let mut at = self.cur;
+        for val in other {
+            at = Some(match at {
+                Some(at) => self.list.insert_after(at, val),
+                None => self.list.push_front(val),
+            });
+        }
+    }
+
+    /// O(k), moves the elements of `other` in right before the cursor, keeping their order,
+    /// they get new indices. On the ghost they go to the back.
+    pub fn splice_before<O: Storage<T>>(&mut self, other: VecList<T, O>) {
+        for val in other {
+            self.insert_before(val);
+        }
+    }
+
+    pub fn as_list(&self) -> &VecList<T, S> {
+        self.list
+    }
+
+    fn next_index(&self) -> Option<usize> {
+        match self.cur {
+            Some(cur) => self.list.next(cur),
+            None => self.list.head,
+        }
+    }
+
+    fn prev_index(&self) -> Option<usize> {
+        match self.cur {
+            Some(cur) => self.list.previous(cur),
+            None => self.list.tail,
+        }
+    }
+}
+
+impl<'a, T> TwinCursorMut<'a, T> {
+    /// `None` on the ghost
+    pub fn index(&self) -> Option<usize> {
+        self.cur.get()
+    }
+
+    /// `None` on the ghost
+    pub fn current(&self) -> Option<&T> {
+        let cur = self.cur.get()?;
+        Some(unsafe { (*self.vals.add(cur)).assume_init_ref() })
+    }
+
+    /// `None` on the ghost
+    pub fn current_mut(&mut self) -> Option<&mut T> {
+        let cur = self.cur.get()?;
+        Some(unsafe { (*self.vals.add(cur)).assume_init_mut() })
+    }
+
+    /// O(1), stays and gives `false` if the other one is on the next element
+    pub fn move_next(&mut self) -> bool {
+        let next = match self.cur.get() {
+            Some(cur) => unsafe { self.links.get_unchecked(cur) }.next(),
+            None => self.head,
+        };
+        self.move_to(next)
+    }
+
+    /// O(1), stays and gives `false` if the other one is on the previous element
+    pub fn move_prev(&mut self) -> bool {
+        let prev = match self.cur.get() {
+            Some(cur) => unsafe { self.links.get_unchecked(cur) }.prev(),
+            None => self.tail,
+        };
+        self.move_to(prev)
+    }
+
+    fn move_to(&mut self, to: Option<usize>) -> bool {
+        /* both can be on the ghost */
+        if to.is_some() && to == self.other.get() {
+            return false;
+        }
+
+        self.cur.set(to);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn twins_never_share_a_slot() {
+        let mut list: VecList<u32> = (0..2).collect();
+        list.twin_cursors_mut(|mut front, mut back| {
+            assert_eq!((front.index(), back.index()), (Some(0), Some(1)));
+            assert!(!front.move_next());
+            assert!(!back.move_prev());
+            assert_eq!((front.index(), back.index()), (Some(0), Some(1)));
+
+            /* around through the ghost is refused too */
+            assert!(back.move_next());
+            assert_eq!(back.index(), None);
+            assert!(!back.move_next());
+            assert!(back.move_prev());
+            assert_eq!(back.index(), Some(1));
+        });
+
+        let mut list: VecList<u32> = VecList::new();
+        list.push_back(0);
+        list.twin_cursors_mut(|mut front, mut back| {
+            assert_eq!((front.index(), back.index()), (Some(0), None));
+            assert!(!back.move_next() && !back.move_prev());
+            /* both on the ghost is fine, no element is shared */
+            assert!(front.move_next());
+            assert_eq!((front.index(), back.index()), (None, None));
+            assert!(back.move_next());
+            assert!(back.current_mut().is_some() && front.current_mut().is_none());
+        });
+    }
+
+    #[test]
+    fn twins_see_each_others_edits() {
+        let mut list: VecList<u32> = (0..3).collect();
+        list.twin_cursors_mut(|mut front, mut back| {
+            let (first, last) = (front.current_mut().unwrap(), back.current_mut().unwrap());
+            *first = 10;
+            *last = 20;
+
+            /* the front one steps off onto the ghost, so the back one can walk onto its slot */
+            assert!(front.move_prev());
+            assert!(back.move_prev() && back.move_prev());
+            assert_eq!(back.current(), Some(&10));
+            /* and from the ghost the front one lands on the back */
+            assert!(front.move_prev());
+            assert_eq!(front.current(), Some(&20));
+        });
+        assert!(list.values().eq(&[10, 1, 20]));
+    }
+
+    fn vals<S: Storage<u32>>(list: &VecList<u32, S>) -> Vec<u32> {
+        list.values().copied().collect()
+    }
+
+    /* a cursor on the head, on the tail, or on the ghost */
+    fn cursor(list: &mut VecList<u32>, at: Option<bool>) -> CursorMut<'_, u32> {
+        match at {
+            Some(true) => list.cursor_front_mut(),
+            Some(false) => list.cursor_back_mut(),
+            None => {
+                let mut cursor = list.cursor_front_mut();
+                cursor.move_prev();
+                cursor
+            }
+        }
+    }
+
+    #[test]
+    fn split_at_the_head_tail_and_ghost() {
+        let cases = [
+            (
+                Some(true),
+                [0].as_slice(),
+                [1, 2, 3].as_slice(),
+                [].as_slice(),
+            ),
+            (Some(false), &[0, 1, 2, 3], &[], &[0, 1, 2]),
+            (None, &[], &[0, 1, 2, 3], &[0, 1, 2, 3]),
+        ];
+        for (at, kept_after, after, before) in cases {
+            let mut list: VecList<u32> = (0..4).collect();
+            let split = cursor(&mut list, at).split_after();
+            assert_eq!(vals(&list), kept_after);
+            assert_eq!(vals(&split), after);
+            crate::tests::check(&list);
+            crate::tests::check(&split);
+
+            let mut list: VecList<u32> = (0..4).collect();
+            let mut cursor = cursor(&mut list, at);
+            let index = cursor.index();
+            let split = cursor.split_before();
+            /* the cursor stays on its element */
+            assert_eq!(cursor.index(), index);
+            assert_eq!(vals(&split), before);
+            crate::tests::check(&list);
+            crate::tests::check(&split);
+        }
+    }
+
+    #[test]
+    fn split_an_empty_list() {
+        let mut list: VecList<u32> = VecList::new();
+        assert!(list.cursor_front_mut().split_after().is_empty());
+        assert!(list.cursor_back_mut().split_before().is_empty());
+        crate::tests::check(&list);
+    }
+
+    #[test]
+    fn splice_at_the_head_tail_and_ghost() {
+        let cases = [
+            (
+                Some(true),
+                [0, 7, 8, 1, 2].as_slice(),
+                [7, 8, 0, 1, 2].as_slice(),
+            ),
+            (Some(false), &[0, 1, 2, 7, 8], &[0, 1, 7, 8, 2]),
+            (None, &[7, 8, 0, 1, 2], &[0, 1, 2, 7, 8]),
+        ];
+        for (at, after, before) in cases {
+            /* from a list on another storage */
+            let mut other = crate::ArrayVecList::<u32, 4>::new_inline();
+            other.extend([6, 7, 8]);
+            other.pop_front();
+
+            let mut list: VecList<u32> = (0..3).collect();
+            cursor(&mut list, at).splice_after(other.clone());
+            assert_eq!(vals(&list), after);
+            crate::tests::check(&list);
+
+            let mut list: VecList<u32> = (0..3).collect();
+            cursor(&mut list, at).splice_before(other);
+            assert_eq!(vals(&list), before);
+            crate::tests::check(&list);
+        }
+    }
+
+    #[test]
+    fn splice_empty_lists() {
+        let mut list: VecList<u32> = (0..3).collect();
+        list.cursor_front_mut().splice_after(VecList::<u32>::new());
+        list.cursor_back_mut().splice_before(VecList::<u32>::new());
+        assert_eq!(vals(&list), [0, 1, 2]);
+        crate::tests::check(&list);
+
+        /* an empty list only has the ghost */
+        let mut list: VecList<u32> = VecList::new();
+        list.cursor_front_mut()
+            .splice_after((0..2).collect::<VecList<u32>>());
+        assert_eq!(vals(&list), [0, 1]);
+        crate::tests::check(&list);
+
+        let mut list: VecList<u32> = VecList::new();
+        list.cursor_back_mut()
+            .splice_before((0..2).collect::<VecList<u32>>());
+        assert_eq!(vals(&list), [0, 1]);
+        crate::tests::check(&list);
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::BoundedList;
+use crate::Iter;
+
+/// Remembers the last `cap` distinct values it let through, and suppresses them.
+/// Suppressed values don't count as insertions, so they don't refresh anything.
+#[derive(Debug, Clone)]
+pub struct RecentDedup<T: Eq + Hash> {
+    order: BoundedList<T>,
+    /* the same values as `order` */
+    seen: HashSet<T>,
+}
+
+impl<T: Eq + Hash + Clone> RecentDedup<T> {
+    pub fn new(cap: usize) -> Self {
+        Self {
+            order: BoundedList::new(cap),
+            seen: HashSet::with_capacity(cap),
+        }
+    }
+
+    /// Average O(1), `true` if `val` was not seen within the window, then it's the newest one
+    pub fn insert(&mut self, val: T) -> bool {
+        if self.seen.contains(&val) {
+            return false;
+        }
+        if self.order.capacity() == 0 {
+            return true;
+        }
+
+        /* forget by hand, so `add` never evicts behind `seen`'s back */
+        if self.order.is_full() {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+
+        self.seen.insert(val.clone());
+        self.order.add(val);
+        true
+    }
+
+    /// Average O(1)
+    pub fn contains(&self, val: &T) -> bool {
+        self.seen.contains(val)
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.order.capacity()
+    }
+
+    pub fn clear(&mut self) {
+        self.order.clear();
+        self.seen.clear();
+    }
+
+    /// Oldest first
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.order.iter()
+    }
+}
@@ -0,0 +1,147 @@
+use std::mem;
+
+use crate::occupancy;
+use crate::Iter;
+use crate::Storage;
+use crate::VecList;
+
+/// The elements not marked for delete, from `VecList::iter_unmarked`
+pub struct Unmarked<'a, T> {
+    iter: Iter<'a, T>,
+    marked: &'a [u64],
+}
+
+impl<T, S: Storage<T>> VecList<T, S> {
+    /// O(1), flags `idx` to be deleted by the next `flush_deleted`, it stays linked and
+    /// keeps its index till then, so running iterations and held indices aren't disturbed.
+    /// Panics if `idx` is deleted.
+    pub fn mark_for_delete(&mut self, idx: usize) {
+        assert!(self.get(idx).is_some(), "invalid key!");
+
+        occupancy::set(&mut self.marked, idx, true);
+    }
+
+    /// O(1), false for deleted ones
+    pub fn is_marked(&self, idx: usize) -> bool {
+        occupancy::get(&self.marked, idx)
+    }
+
+    /// O(1), takes the flag back
+    pub fn unmark(&mut self, idx: usize) {
+        occupancy::set(&mut self.marked, idx, false);
+    }
+
+    /// O(cap / 64 + k), deletes and drops the marked ones in index order like `delete_many`,
+    /// gives how many were deleted
+    pub fn flush_deleted(&mut self) -> usize {
+        let mut marked = mem::take(&mut self.marked);
+        let indices: Vec<usize> = occupancy::ones(&marked).collect();
+        let deleted = self.delete_many(&indices);
+
+        /* keeps the buffer */
+        marked.clear();
+        self.marked = marked;
+        deleted
+    }
+
+    /// O(n), like `iter` but skips the marked ones
+    pub fn iter_unmarked(&self) -> Unmarked<'_, T> {
+        Unmarked {
+            iter: self.iter(),
+            marked: &self.marked,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Unmarked<'a, T> {
+    type Item = (&'a T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .find(|&(_, idx)| !occupancy::get(self.marked, idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Unmarked<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter
+            .rfind(|&(_, idx)| !occupancy::get(self.marked, idx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unmarked(list: &VecList<u32>) -> Vec<u32> {
+        list.iter_unmarked().map(|(val, _)| *val).collect()
+    }
+
+    #[test]
+    fn marks_follow_the_remap() {
+        let mut list: VecList<u32> = (0..6).collect();
+        list.delete(1);
+        list.swap_links(0, 5);
+        list.mark_for_delete(5);
+        list.mark_for_delete(3);
+        assert_eq!(unmarked(&list), [2, 4, 0]);
+
+        let remap = list.optimize_for_iteration();
+        assert!(list.values().eq(&[5, 2, 3, 4, 0]));
+        assert_eq!((remap[5], remap[3], remap[1]), (Some(0), Some(2), None));
+        /* the marks moved with their values, nothing else is marked */
+        let marked: Vec<usize> = (0..list.cap()).filter(|&idx| list.is_marked(idx)).collect();
+        assert_eq!(marked, [0, 2]);
+        assert_eq!(unmarked(&list), [2, 4, 0]);
+        crate::tests::check(&list);
+
+        assert_eq!(list.flush_deleted(), 2);
+        assert!(list.values().eq(&[2, 4, 0]));
+    }
+
+    #[test]
+    fn flush_clears_the_marks() {
+        let mut list: VecList<u32> = (0..4).collect();
+        list.mark_for_delete(1);
+        list.mark_for_delete(2);
+        list.unmark(2);
+        assert!(list.is_marked(1) && !list.is_marked(2));
+
+        assert_eq!(list.flush_deleted(), 1);
+        assert!(list.values().eq(&[0, 2, 3]));
+        crate::tests::check(&list);
+
+        /* the freed slot is reused unmarked, and a second flush has nothing to do */
+        assert_eq!(list.push_back(4), 1);
+        assert!(!list.is_marked(1));
+        assert_eq!(list.flush_deleted(), 0);
+        assert!(list.values().eq(&[0, 2, 3, 4]));
+    }
+
+    #[test]
+    fn iter_unmarked_from_both_ends() {
+        let mut list: VecList<u32> = (0..5).collect();
+        assert_eq!(unmarked(&list), [0, 1, 2, 3, 4]);
+
+        for idx in [0, 2, 4] {
+            list.mark_for_delete(idx);
+        }
+        assert_eq!(unmarked(&list), [1, 3]);
+        let back: Vec<u32> = list.iter_unmarked().rev().map(|(val, _)| *val).collect();
+        assert_eq!(back, [3, 1]);
+        /* the marked ones are still in the list */
+        assert_eq!(list.len(), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid key!")]
+    fn marking_a_deleted_slot_panics() {
+        let mut list: VecList<u32> = (0..2).collect();
+        list.delete(0);
+        list.mark_for_delete(0);
+    }
+}
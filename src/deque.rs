@@ -0,0 +1,268 @@
+use std::ops;
+
+use crate::Values;
+use crate::ValuesMut;
+use crate::VecList;
+
+/// A double-ended queue with `VecDeque`-like method names backed by `VecList`.
+/// Positional access walks the list from the nearer end, so it is O(n).
+///
+/// Use `as_list_mut` when you need stable indices, e.g. to delete in the middle in O(1).
+#[derive(Debug, Default, Clone)]
+pub struct Deque<T> {
+    list: VecList<T>,
+}
+
+impl<T> Deque<T> {
+    pub const fn new() -> Self {
+        Self {
+            list: VecList::new(),
+        }
+    }
+
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            list: VecList::with_capacity(cap),
+        }
+    }
+
+    /// Average O(1)
+    pub fn push_back(&mut self, val: T) {
+        self.list.push_back(val);
+    }
+
+    /// Average O(1)
+    pub fn push_front(&mut self, val: T) {
+        self.list.push_front(val);
+    }
+
+    /// O(1)
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+
+    /// O(1)
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    /// O(1)
+    pub fn front(&self) -> Option<&T> {
+        self.list.front().map(|(val, _)| val)
+    }
+
+    /// O(1)
+    pub fn back(&self) -> Option<&T> {
+        self.list.back().map(|(val, _)| val)
+    }
+
+    /// O(1)
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.list.front_mut().map(|(val, _)| val)
+    }
+
+    /// O(1)
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.list.back_mut().map(|(val, _)| val)
+    }
+
+    /// O(n)
+    pub fn get(&self, pos: usize) -> Option<&T> {
+        let idx = self.index_of_position(pos)?;
+        self.list.get(idx)
+    }
+
+    /// O(n)
+    pub fn get_mut(&mut self, pos: usize) -> Option<&mut T> {
+        let idx = self.index_of_position(pos)?;
+        self.list.get_mut(idx)
+    }
+
+    /// O(n)
+    pub fn remove(&mut self, pos: usize) -> Option<T> {
+        let idx = self.index_of_position(pos)?;
+        self.list.delete(idx)
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+
+    pub fn iter(&self) -> Values<'_, T> {
+        self.list.values()
+    }
+
+    pub fn iter_mut(&mut self) -> ValuesMut<'_, T> {
+        self.list.values_mut()
+    }
+
+    pub fn as_list(&self) -> &VecList<T> {
+        &self.list
+    }
+
+    pub fn as_list_mut(&mut self) -> &mut VecList<T> {
+        &mut self.list
+    }
+
+    pub fn into_list(self) -> VecList<T> {
+        self.list
+    }
+
+    fn index_of_position(&self, pos: usize) -> Option<usize> {
+        if pos >= self.len() {
+            return None;
+        }
+
+        if pos < self.len() / 2 {
+            self.list.iter().nth(pos).map(|(_, idx)| idx)
+        } else {
+            self.list
+                .iter()
+                .rev()
+                .nth(self.len() - 1 - pos)
+                .map(|(_, idx)| idx)
+        }
+    }
+}
+
+impl<T: PartialEq> Deque<T> {
+    pub fn contains(&self, x: &T) -> bool {
+        self.list.contains(x)
+    }
+}
+
+impl<T> From<VecList<T>> for Deque<T> {
+    fn from(list: VecList<T>) -> Self {
+        Self { list }
+    }
+}
+
+impl<T> ops::Index<usize> for Deque<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("out of bounds!")
+    }
+}
+
+impl<T> ops::IndexMut<usize> for Deque<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("out of bounds!")
+    }
+}
+
+impl<T> IntoIterator for Deque<T> {
+    type Item = T;
+
+    type IntoIter = crate::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Deque<T> {
+    type Item = &'a T;
+
+    type IntoIter = Values<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Deque<T> {
+    type Item = &'a mut T;
+
+    type IntoIter = ValuesMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* pushed at both ends and fragmented, so slot order is not list order */
+    fn deque(len: u32) -> Deque<u32> {
+        let mut deque = Deque::new();
+        for i in 0..len {
+            match i % 3 {
+                0 => deque.push_front(i),
+                _ => deque.push_back(i),
+            }
+        }
+        if len > 4 {
+            deque.remove(2);
+            deque.push_front(100);
+        }
+        deque
+    }
+
+    #[test]
+    fn positions_from_either_end_match_a_front_walk() {
+        for len in 0..12 {
+            let mut deque = deque(len);
+            let walked: Vec<u32> = deque.iter().copied().collect();
+            assert_eq!(walked.len(), deque.len());
+
+            for (pos, val) in walked.iter().enumerate() {
+                assert_eq!(deque.get(pos), Some(val));
+                assert_eq!(deque[pos], *val);
+            }
+            assert_eq!(deque.get(walked.len()), None);
+            assert_eq!(deque.get(usize::MAX), None);
+
+            for pos in 0..walked.len() {
+                deque[pos] += 1000;
+            }
+            assert!(deque
+                .iter()
+                .map(|val| val - 1000)
+                .eq(walked.iter().copied()));
+        }
+    }
+
+    #[test]
+    fn remove_from_either_half() {
+        let mut deque = deque(9);
+        let mut walked: Vec<u32> = deque.iter().copied().collect();
+        for pos in [0, 6, 3, 4, 1] {
+            assert_eq!(deque.remove(pos), Some(walked.remove(pos)));
+            assert!(deque.iter().eq(&walked));
+        }
+        assert_eq!(deque.remove(walked.len()), None);
+        crate::tests::check(deque.as_list());
+    }
+
+    #[test]
+    fn both_ends() {
+        let mut deque = Deque::with_capacity(2);
+        assert_eq!((deque.front(), deque.back()), (None, None));
+        deque.push_back(1);
+        deque.push_front(0);
+        deque.push_back(2);
+        *deque.back_mut().unwrap() += 10;
+        *deque.front_mut().unwrap() += 10;
+        assert_eq!((deque.front(), deque.back()), (Some(&10), Some(&12)));
+        assert_eq!(deque.pop_back(), Some(12));
+        assert_eq!(deque.pop_front(), Some(10));
+        assert!(deque.iter().eq(&[1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds!")]
+    fn index_past_the_back_panics() {
+        let _ = deque(3)[3];
+    }
+}
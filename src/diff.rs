@@ -0,0 +1,220 @@
+use crate::Storage;
+use crate::VecList;
+
+/// One step of an edit script from `VecList::diff`, positions are in list order,
+/// so a script made on one replica applies to another one holding the same values
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Edit<T> {
+    /// Skips the next `n` elements
+    Keep(usize),
+    /// Removes the next `n` elements
+    Delete(usize),
+    /// Adds one before the next element
+    Insert(T),
+}
+
+/* one element of the script, `Insert` holds the position in the new values */
+enum Op {
+    Keep,
+    Delete,
+    Insert(usize),
+}
+
+impl<T: PartialEq + Clone, S: Storage<T>> VecList<T, S> {
+    /// O((n + m) d) time and memory, for `d` the number of inserted and deleted ones.
+    /// A shortest script (Myers) which turns `self` into `other` by `apply`.
+    pub fn diff<O: Storage<T>>(&self, other: &VecList<T, O>) -> Vec<Edit<T>> {
+        let old: Vec<&T> = self.values().collect();
+        let new: Vec<&T> = other.values().collect();
+
+        let mut edits = Vec::new();
+        for op in shortest_script(&old, &new) {
+            match (op, edits.last_mut()) {
+                (Op::Keep, Some(Edit::Keep(n))) | (Op::Delete, Some(Edit::Delete(n))) => *n += 1,
+                (Op::Keep, _) => edits.push(Edit::Keep(1)),
+                (Op::Delete, _) => edits.push(Edit::Delete(1)),
+                (Op::Insert(j), _) => edits.push(Edit::Insert(new[j].clone())),
+            }
+        }
+
+        edits
+    }
+}
+
+impl<T, S: Storage<T>> VecList<T, S> {
+    /// O(n + edits), runs a script from `diff` front to back, the kept elements keep their indices.
+    /// Panics if it runs past the back.
+    pub fn apply<I: IntoIterator<Item = Edit<T>>>(&mut self, edits: I) {
+        let mut cur = self.head;
+        for edit in edits {
+            match edit {
+                Edit::Keep(n) => {
+                    for _ in 0..n {
+                        cur = self.next(cur.expect("edits don't fit!"));
+                    }
+                }
+                Edit::Delete(n) => {
+                    for _ in 0..n {
+                        let idx = cur.expect("edits don't fit!");
+                        cur = self.next(idx);
+                        self.delete(idx);
+                    }
+                }
+                Edit::Insert(val) => {
+                    match cur {
+                        Some(idx) => self.insert_before(idx, val),
+                        None => self.push_back(val),
+                    };
+                }
+            }
+        }
+    }
+}
+
+/// Myers' greedy search over the diagonals `k = x - y`, keeping the furthest `x` of each
+/// diagonal per round, then walking the rounds back from the end
+fn shortest_script<T: PartialEq>(old: &[&T], new: &[&T]) -> Vec<Op> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let offset = n + m + 1;
+    let at = |k: isize| (k + offset) as usize;
+
+    let mut v = vec![0isize; 2 * offset as usize + 1];
+    let mut trace = Vec::new();
+    'search: for d in 0..=n + m {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[at(k - 1)] < v[at(k + 1)]) {
+                v[at(k + 1)]
+            } else {
+                v[at(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+
+            v[at(k)] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let (d, k) = (d as isize, x - y);
+        let prev_k = if k == -d || (k != d && v[at(k - 1)] < v[at(k + 1)]) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = v[at(prev_k)];
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            ops.push(Op::Keep);
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            ops.push(if x == prev_x {
+                Op::Insert((y - 1) as usize)
+            } else {
+                Op::Delete
+            });
+        }
+
+        (x, y) = (prev_x, prev_y);
+    }
+
+    ops.reverse();
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(vals: &[char]) -> VecList<char> {
+        vals.iter().copied().collect()
+    }
+
+    /* diffs, applies, and checks the result and that the script is a shortest one */
+    fn round_trip(old: &str, new: &str, changes: usize) -> Vec<Edit<char>> {
+        let (old, new): (Vec<char>, Vec<char>) = (old.chars().collect(), new.chars().collect());
+        let mut from = list(&old);
+        let edits = from.diff(&list(&new));
+
+        let changed: usize = edits
+            .iter()
+            .map(|edit| match edit {
+                Edit::Keep(_) => 0,
+                Edit::Delete(n) => *n,
+                Edit::Insert(_) => 1,
+            })
+            .sum();
+        assert_eq!(changed, changes);
+
+        from.apply(edits.clone());
+        assert!(from.values().eq(&new));
+        crate::tests::check(&from);
+        edits
+    }
+
+    #[test]
+    fn empty_to_full_and_back() {
+        assert_eq!(
+            round_trip("", "ab", 2),
+            [Edit::Insert('a'), Edit::Insert('b')]
+        );
+        assert_eq!(round_trip("ab", "", 2), [Edit::Delete(2)]);
+        assert_eq!(round_trip("", "", 0), []);
+    }
+
+    #[test]
+    fn identical_lists_keep_everything() {
+        assert_eq!(round_trip("abc", "abc", 0), [Edit::Keep(3)]);
+    }
+
+    #[test]
+    fn shortest_scripts() {
+        /* the classic example from the paper */
+        round_trip("abcabba", "cbabac", 5);
+        round_trip("abc", "xyz", 6);
+        round_trip("abcd", "acd", 1);
+        round_trip("acd", "abcd", 1);
+        round_trip("abab", "baba", 2);
+    }
+
+    #[test]
+    fn kept_ones_keep_their_indices() {
+        let mut from = list(&['a', 'b', 'c', 'd', 'e']);
+        /* fragment it, so list order and slot order differ */
+        from.delete(1);
+        from.delete(3);
+        let x = from.push_front('x');
+        let kept: Vec<(char, usize)> = from
+            .iter()
+            .filter(|(val, _)| "ace".contains(**val))
+            .map(|(val, idx)| (*val, idx))
+            .collect();
+
+        let to = list(&['a', 'y', 'c', 'e', 'z']);
+        from.apply(from.diff(&to));
+        assert!(from.values().eq(to.values()));
+        /* the deleted one's slot may be reused by an insert */
+        assert_ne!(from.get(x), Some(&'x'));
+        for (val, idx) in kept {
+            assert_eq!(from.get(idx), Some(&val));
+        }
+        crate::tests::check(&from);
+    }
+
+    #[test]
+    #[should_panic(expected = "edits don't fit!")]
+    fn apply_past_the_back_panics() {
+        list(&['a']).apply([Edit::Keep(2)]);
+    }
+}
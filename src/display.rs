@@ -0,0 +1,117 @@
+use core::fmt;
+
+use crate::Storage;
+use crate::VecList;
+use crate::VecStorage;
+
+/// Formats the values with `T: Display`, from `VecList::display`.
+/// By default it looks like `a -> b -> c`, without a trailing newline.
+pub struct ListDisplay<'a, T, S: Storage<T> = VecStorage<T>> {
+    list: &'a VecList<T, S>,
+    sep: &'a str,
+    open: &'a str,
+    close: &'a str,
+}
+
+/// The slot table, one line per slot with its index, from `VecList::dump`
+pub struct Dump<'a, T, S: Storage<T> = VecStorage<T>> {
+    list: &'a VecList<T, S>,
+}
+
+impl<T, S: Storage<T>> VecList<T, S> {
+    pub fn display(&self) -> ListDisplay<'_, T, S> {
+        ListDisplay {
+            list: self,
+            sep: " -> ",
+            open: "",
+            close: "",
+        }
+    }
+
+    /// For debugging the links
+    pub fn dump(&self) -> Dump<'_, T, S> {
+        Dump { list: self }
+    }
+}
+
+impl<'a, T, S: Storage<T>> ListDisplay<'a, T, S> {
+    /// Between two values
+    pub fn sep(mut self, sep: &'a str) -> Self {
+        self.sep = sep;
+        self
+    }
+
+    /// Before the first value and after the last one, also written when it's empty
+    pub fn brackets(mut self, open: &'a str, close: &'a str) -> Self {
+        self.open = open;
+        self.close = close;
+        self
+    }
+}
+
+impl<'a, T: fmt::Display, S: Storage<T>> fmt::Display for ListDisplay<'a, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.open)?;
+
+        let mut sep = "";
+        for val in self.list.values() {
+            write!(f, "{}{}", sep, val)?;
+            sep = self.sep;
+        }
+
+        f.write_str(self.close)
+    }
+}
+
+impl<'a, T: fmt::Debug, S: Storage<T>> fmt::Display for Dump<'a, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = self.list;
+        writeln!(
+            f,
+            "head: {:?}, tail: {:?}, deleted_tail: {:?}, deleted_head: {:?}, slot_reuse: {:?}, len: {}",
+            list.head, list.tail, list.deleted_tail, list.deleted_head, list.reuse, list.len
+        )?;
+
+        let slots = list.storage.links().iter().zip(list.storage.vals());
+        for (idx, (link, val)) in slots.enumerate() {
+            if link.has_value() {
+                writeln!(
+                    f,
+                    "{}: value {:?}, next: {:?}, prev: {:?}",
+                    idx,
+                    unsafe { val.assume_init_ref() },
+                    link.next(),
+                    link.prev()
+                )?;
+            } else {
+                writeln!(f, "{}: deleted, prev: {:?}", idx, link.deleted_prev())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SlotReuse;
+
+    #[test]
+    fn dump_shows_both_ends_of_the_deleted_chain() {
+        let mut list: VecList<u32> = (0..4).collect();
+        list.set_slot_reuse(SlotReuse::Fifo);
+        list.delete(1);
+        list.delete(2);
+
+        assert_eq!(
+            list.dump().to_string(),
+            "head: Some(0), tail: Some(3), deleted_tail: Some(1), deleted_head: Some(2), \
+             slot_reuse: Fifo, len: 2\n\
+             0: value 0, next: Some(3), prev: None\n\
+             1: deleted, prev: Some(2)\n\
+             2: deleted, prev: None\n\
+             3: value 3, next: None, prev: Some(0)\n"
+        );
+    }
+}
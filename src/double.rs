@@ -0,0 +1,68 @@
+use std::mem;
+
+use crate::Drain;
+use crate::VecList;
+
+/// Two lists for frame based work: this frame's is read and drained
+/// while the next frame's is built, `swap` flips them and keeps both buffers
+#[derive(Debug, Default, Clone)]
+pub struct DoubleBuffered<T> {
+    current: VecList<T>,
+    next: VecList<T>,
+}
+
+impl<T> DoubleBuffered<T> {
+    pub const fn new() -> Self {
+        Self {
+            current: VecList::new(),
+            next: VecList::new(),
+        }
+    }
+
+    /// Each list gets room for `cap`
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            current: VecList::with_capacity(cap),
+            next: VecList::with_capacity(cap),
+        }
+    }
+
+    /// Average O(1), the index is in the next frame's list
+    pub fn push_next(&mut self, val: T) -> usize {
+        self.next.push_back(val)
+    }
+
+    /// O(k), the next frame's list becomes the current one,
+    /// what's left of the current one is dropped and its slots are kept for the new next one
+    pub fn swap(&mut self) {
+        mem::swap(&mut self.current, &mut self.next);
+        self.next.clear();
+    }
+
+    pub fn current(&self) -> &VecList<T> {
+        &self.current
+    }
+
+    pub fn current_mut(&mut self) -> &mut VecList<T> {
+        &mut self.current
+    }
+
+    pub fn next(&self) -> &VecList<T> {
+        &self.next
+    }
+
+    pub fn next_mut(&mut self) -> &mut VecList<T> {
+        &mut self.next
+    }
+
+    /// Takes this frame's elements front to back, along with the next frame's list for follow-ups
+    pub fn drain_current(&mut self) -> (Drain<'_, T>, &mut VecList<T>) {
+        (self.current.drain(), &mut self.next)
+    }
+
+    /// Both lists
+    pub fn clear(&mut self) {
+        self.current.clear();
+        self.next.clear();
+    }
+}
@@ -0,0 +1,106 @@
+use core::fmt;
+use std::error::Error;
+use std::mem;
+
+use crate::Storage;
+use crate::VecList;
+
+/// Why a `try_` method failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VecListError {
+    /// The index is not less than `cap`
+    OutOfRange,
+    /// The index is of a deleted slot
+    SlotDeleted,
+    /// There is no deleted slot and the storage can't grow, or the allocation failed
+    CapacityExceeded,
+}
+
+impl fmt::Display for VecListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::OutOfRange => "index out of range",
+            Self::SlotDeleted => "slot is deleted",
+            Self::CapacityExceeded => "capacity exceeded",
+        })
+    }
+}
+
+impl Error for VecListError {}
+
+/// Like the panicking or `Option` ones, but say what is wrong.
+/// The ones taking a value give it back on error.
+impl<T, S: Storage<T>> VecList<T, S> {
+    pub fn try_get(&self, idx: usize) -> Result<&T, VecListError> {
+        self.check(idx)?;
+        Ok(unsafe { self.val(idx) })
+    }
+
+    pub fn try_get_mut(&mut self, idx: usize) -> Result<&mut T, VecListError> {
+        self.check(idx)?;
+        Ok(unsafe { self.val_mut(idx) })
+    }
+
+    /// O(1)
+    pub fn try_delete(&mut self, idx: usize) -> Result<T, VecListError> {
+        self.check(idx)?;
+        self.delete(idx).ok_or(VecListError::SlotDeleted)
+    }
+
+    /// O(1), gives the old value back
+    pub fn try_replace(&mut self, idx: usize, val: T) -> Result<T, (VecListError, T)> {
+        match self.try_get_mut(idx) {
+            Ok(old) => Ok(mem::replace(old, val)),
+            Err(err) => Err((err, val)),
+        }
+    }
+
+    /// Average O(1), fails instead of panicking or aborting if the storage can't grow
+    pub fn try_push_back(&mut self, val: T) -> Result<usize, (VecListError, T)> {
+        match self.reserve_room() {
+            Ok(()) => Ok(self.push_back(val)),
+            Err(err) => Err((err, val)),
+        }
+    }
+
+    /// Average O(1), fails instead of panicking or aborting if the storage can't grow
+    pub fn try_push_front(&mut self, val: T) -> Result<usize, (VecListError, T)> {
+        match self.reserve_room() {
+            Ok(()) => Ok(self.push_front(val)),
+            Err(err) => Err((err, val)),
+        }
+    }
+
+    /// Average O(1), fails instead of panicking or aborting if the storage can't grow
+    pub fn try_insert_after(&mut self, idx: usize, val: T) -> Result<usize, (VecListError, T)> {
+        match self.check(idx).and_then(|_| self.reserve_room()) {
+            Ok(()) => Ok(self.insert_after(idx, val)),
+            Err(err) => Err((err, val)),
+        }
+    }
+
+    /// Average O(1), fails instead of panicking or aborting if the storage can't grow
+    pub fn try_insert_before(&mut self, idx: usize, val: T) -> Result<usize, (VecListError, T)> {
+        match self.check(idx).and_then(|_| self.reserve_room()) {
+            Ok(()) => Ok(self.insert_before(idx, val)),
+            Err(err) => Err((err, val)),
+        }
+    }
+
+    fn check(&self, idx: usize) -> Result<(), VecListError> {
+        match self.storage.links().get(idx) {
+            Some(link) if link.has_value() => Ok(()),
+            Some(_) => Err(VecListError::SlotDeleted),
+            None => Err(VecListError::OutOfRange),
+        }
+    }
+
+    /// Makes sure the next `alloc` neither panics nor allocates
+    fn reserve_room(&mut self) -> Result<(), VecListError> {
+        if self.deleted_tail.is_some() {
+            Ok(())
+        } else {
+            self.try_reserve(1)
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::ops;
+use std::slice;
+
+use crate::Storage;
+use crate::VecList;
+
+/// The values of a `VecList` packed in list order without links, from `VecList::freeze`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FrozenVecList<T> {
+    vals: Box<[T]>,
+}
+
+impl<T, S: Storage<T>> VecList<T, S> {
+    /// O(n), indices are gone, use positions from front to back instead
+    pub fn freeze(self) -> FrozenVecList<T> {
+        FrozenVecList {
+            vals: self.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> FrozenVecList<T> {
+    /// O(n), the new indices are `0..len` in order
+    pub fn thaw(self) -> VecList<T> {
+        self.vals.into_vec().into_iter().collect()
+    }
+
+    /// O(1)
+    pub fn get(&self, pos: usize) -> Option<&T> {
+        self.vals.get(pos)
+    }
+
+    /// O(1)
+    pub fn get_mut(&mut self, pos: usize) -> Option<&mut T> {
+        self.vals.get_mut(pos)
+    }
+
+    pub fn len(&self) -> usize {
+        self.vals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vals.is_empty()
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.vals.iter()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.vals
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.vals
+    }
+}
+
+impl<T> ops::Index<usize> for FrozenVecList<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("out of bounds!")
+    }
+}
+
+impl<T> ops::IndexMut<usize> for FrozenVecList<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("out of bounds!")
+    }
+}
+
+impl<'a, T> IntoIterator for &'a FrozenVecList<T> {
+    type Item = &'a T;
+
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,266 @@
+use crate::Iter;
+use crate::VecList;
+
+/* labels of the virtual ends, real labels are strictly between them */
+const START: u64 = 0;
+const END: u64 = u64::MAX;
+/* room left after the back or before the front, so runs of pushes rarely relabel */
+const GAP: u64 = 1 << 32;
+
+/// A `VecList` whose elements carry increasing labels, so `precedes` is O(1).
+///
+/// When two neighbors have no label left between them, the ones around them are spread out again,
+/// in the smallest aligned range of labels which is sparse enough (Bender et al.),
+/// which is amortized O(log n) per insert.
+#[derive(Debug, Default, Clone)]
+pub struct LabeledList<T> {
+    list: VecList<T>,
+    /* by slot index, stale for deleted slots */
+    labels: Vec<u64>,
+}
+
+impl<T> LabeledList<T> {
+    pub const fn new() -> Self {
+        Self {
+            list: VecList::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    /// Amortized O(1)
+    pub fn push_back(&mut self, val: T) -> usize {
+        let label = self.label_between(self.list.tail, None);
+        let idx = self.list.push_back(val);
+        self.set_label(idx, label)
+    }
+
+    /// Amortized O(1)
+    pub fn push_front(&mut self, val: T) -> usize {
+        let label = self.label_between(None, self.list.head);
+        let idx = self.list.push_front(val);
+        self.set_label(idx, label)
+    }
+
+    /// Amortized O(log n), panics if `idx` is deleted
+    pub fn insert_after(&mut self, idx: usize, val: T) -> usize {
+        assert!(self.list.get(idx).is_some(), "invalid key!");
+
+        let label = self.label_between(Some(idx), self.list.next(idx));
+        let new_idx = self.list.insert_after(idx, val);
+        self.set_label(new_idx, label)
+    }
+
+    /// Amortized O(log n), panics if `idx` is deleted
+    pub fn insert_before(&mut self, idx: usize, val: T) -> usize {
+        assert!(self.list.get(idx).is_some(), "invalid key!");
+
+        let label = self.label_between(self.list.previous(idx), Some(idx));
+        let new_idx = self.list.insert_before(idx, val);
+        self.set_label(new_idx, label)
+    }
+
+    /// O(1)
+    pub fn delete(&mut self, idx: usize) -> Option<T> {
+        self.list.delete(idx)
+    }
+
+    /// O(1)
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    /// O(1)
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+
+    /// O(1), whether `a` is before `b`, panics if either one is deleted
+    pub fn precedes(&self, a: usize, b: usize) -> bool {
+        assert!(
+            self.list.get(a).is_some() && self.list.get(b).is_some(),
+            "invalid key!"
+        );
+
+        self.labels[a] < self.labels[b]
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        self.list.get(idx)
+    }
+
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        self.list.get_mut(idx)
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.list.clear();
+        self.labels.clear();
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.list.iter()
+    }
+
+    pub fn as_list(&self) -> &VecList<T> {
+        &self.list
+    }
+
+    pub fn into_list(self) -> VecList<T> {
+        self.list
+    }
+
+    fn set_label(&mut self, idx: usize, label: u64) -> usize {
+        if idx >= self.labels.len() {
+            self.labels.resize(idx + 1, START);
+        }
+        self.labels[idx] = label;
+        idx
+    }
+
+    fn label(&self, idx: Option<usize>, none: u64) -> u64 {
+        idx.map_or(none, |idx| self.labels[idx])
+    }
+
+    /// A free label between the neighbors `prev` and `next`, relabels around `prev` if there is none
+    fn label_between(&mut self, prev: Option<usize>, next: Option<usize>) -> u64 {
+        if self.label(next, END) - self.label(prev, START) < 2 {
+            self.relabel_around(prev);
+        }
+
+        let lo = self.label(prev, START);
+        let hi = self.label(next, END);
+        match (prev, next) {
+            (Some(_), None) if hi - lo > GAP => lo + GAP,
+            (None, Some(_)) if hi - lo > GAP => hi - GAP,
+            _ => lo + (hi - lo) / 2,
+        }
+    }
+
+    /// Spreads out evenly the `k` ones labeled in the smallest aligned window of `2^i` labels
+    /// around `base`'s label, or around `START` if it's `None`, for which `(k + 1)^2 < 2^i`,
+    /// so there is room right after `base` afterwards
+    fn relabel_around(&mut self, base: Option<usize>) {
+        let label = self.label(base, START) as u128;
+        /* the ones in the window so far, from `first` to `last` in list order */
+        let (mut first, mut last, mut k) = match base {
+            Some(base) => (Some(base), Some(base), 1),
+            None => (None, None, 0),
+        };
+
+        for bits in 1..=u64::BITS {
+            let size = 1u128 << bits;
+            let start = label & !(size - 1);
+            let end = start + size;
+
+            while let Some(prev) = first.and_then(|idx| self.list.previous(idx)) {
+                if (self.labels[prev] as u128) < start {
+                    break;
+                }
+                first = Some(prev);
+                k += 1;
+            }
+            loop {
+                let next = match last {
+                    Some(idx) => self.list.next(idx),
+                    None => self.list.head,
+                };
+                match next {
+                    Some(next) if (self.labels[next] as u128) < end => {
+                        first.get_or_insert(next);
+                        last = Some(next);
+                        k += 1;
+                    }
+                    _ => break,
+                }
+            }
+
+            if (k + 1) * (k + 1) < size {
+                let gap = size / (k + 1);
+                let mut node = first;
+                for i in 1..=k {
+                    let idx = node.expect("relabeled past the window!");
+                    self.labels[idx] = (start + i * gap) as u64;
+                    node = self.list.next(idx);
+                }
+                return;
+            }
+        }
+
+        panic!("too many elements to label!");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* labels strictly increase front to back, strictly between the virtual ends */
+    fn check<T>(list: &LabeledList<T>) {
+        let mut prev = START;
+        for (_, idx) in list.iter() {
+            assert!(prev < list.labels[idx], "labels out of order!");
+            prev = list.labels[idx];
+        }
+        assert!(prev < END, "label at the end!");
+    }
+
+    #[test]
+    fn churns_at_the_back() {
+        let mut list = LabeledList::new();
+        let a = list.push_back(0);
+        let b = list.push_back(1);
+        /* as if the back had been pushed to for a long time */
+        list.labels[a] = END - 3;
+        list.labels[b] = END - 2;
+
+        for i in 2..10_000 {
+            let idx = list.push_back(i);
+            assert_eq!(list.pop_front(), Some(i - 2));
+            assert!(list.precedes(list.as_list().head.unwrap(), idx));
+            check(&list);
+        }
+    }
+
+    #[test]
+    fn churns_at_the_front() {
+        let mut list = LabeledList::new();
+        let a = list.push_front(0);
+        let b = list.push_front(1);
+        list.labels[a] = START + 2;
+        list.labels[b] = START + 1;
+
+        for i in 2..10_000 {
+            let idx = list.push_front(i);
+            assert_eq!(list.pop_back(), Some(i - 2));
+            assert!(list.precedes(idx, list.as_list().tail.unwrap()));
+            check(&list);
+        }
+    }
+
+    #[test]
+    fn inserts_in_one_spot() {
+        let mut list = LabeledList::new();
+        let first = list.push_back(0);
+        list.push_back(1);
+
+        /* halves the same gap every time, so it runs out of labels quickly */
+        for i in 2..2_000 {
+            list.insert_after(first, i);
+            check(&list);
+        }
+        let mut last = list.as_list().tail.unwrap();
+        for i in 0..2_000 {
+            last = list.insert_before(last, i);
+            check(&list);
+        }
+        assert_eq!(list.len(), 4_000);
+    }
+}
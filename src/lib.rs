@@ -1,10 +1,130 @@
 #![allow(clippy::collapsible_else_if)]
 use core::fmt;
-use std::hint::unreachable_unchecked;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::iter::Chain;
+use std::marker::PhantomData;
+use std::mem;
+use std::mem::MaybeUninit;
 use std::ops;
 use std::ptr;
 
+mod aligned;
+#[cfg(feature = "tokio")]
+mod async_queue;
+mod batches;
 mod bounded;
+mod boxed;
+mod branded;
+mod cache;
+pub mod compat;
+mod cursor;
+mod dedup;
+mod deferred;
+mod deque;
+mod diff;
+mod display;
+mod double;
+mod error;
+mod frozen;
+mod labeled;
+mod limiter;
+mod lines;
+mod link;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+mod multi;
+mod occupancy;
+mod ordered;
+mod positions;
+#[cfg(feature = "python")]
+pub mod python;
+mod queue;
+#[cfg(feature = "rayon")]
+mod rayon_impl;
+#[cfg(feature = "arc-swap")]
+mod read_mostly;
+mod resume;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod snapshot;
+mod sorted;
+mod stable;
+#[cfg(feature = "critical-section")]
+mod static_list;
+mod storage;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod testing;
+#[cfg(test)]
+mod tests;
+#[cfg(feature = "wasm")]
+mod wasm_impl;
+mod window;
+
+pub use aligned::AlignedVecList;
+pub use aligned::CacheAligned;
+#[cfg(feature = "tokio")]
+pub use async_queue::AsyncBoundedQueue;
+pub use batches::BatchesMut;
+pub use batches::LendingIterator;
+pub use bounded::ArrayBoundedList;
+pub use bounded::BoundedList;
+pub use bounded::DropEvicted;
+pub use bounded::EvictionPolicy;
+pub use bounded::OnEvict;
+pub use boxed::BoxedVecList;
+pub use branded::BrandedIndex;
+pub use branded::BrandedList;
+pub use cache::Cache;
+pub use cache::CacheEntry;
+pub use cache::CachePolicy;
+pub use cache::CacheStats;
+pub use cache::Fifo;
+pub use cache::Lru;
+pub use cache::LruCache;
+pub use cache::OccupiedCacheEntry;
+pub use cache::VacantCacheEntry;
+pub use cursor::CursorMut;
+pub use cursor::TwinCursorMut;
+pub use dedup::RecentDedup;
+pub use deferred::Unmarked;
+pub use deque::Deque;
+pub use diff::Edit;
+pub use display::Dump;
+pub use display::ListDisplay;
+pub use double::DoubleBuffered;
+pub use error::VecListError;
+pub use frozen::FrozenVecList;
+pub use labeled::LabeledList;
+pub use limiter::RateLimiter;
+pub use lines::LineList;
+pub use lines::Lines;
+pub use link::Link;
+pub use multi::MultiIter;
+pub use multi::MultiList;
+pub use occupancy::Unordered;
+pub use ordered::OccupiedOrderedMapEntry;
+pub use ordered::OrderedMap;
+pub use ordered::OrderedMapEntry;
+pub use ordered::VacantOrderedMapEntry;
+pub use positions::Positions;
+pub use queue::Queue;
+#[cfg(feature = "arc-swap")]
+pub use read_mostly::ReadMostly;
+pub use resume::IterToken;
+pub use resume::Resume;
+pub use sorted::SortedList;
+pub use sorted::SortedRange;
+pub use stable::IterStable;
+#[cfg(feature = "critical-section")]
+pub use static_list::StaticVecList;
+pub use storage::ArrayStorage;
+pub use storage::Growth;
+pub use storage::Storage;
+pub use storage::VecStorage;
+pub use window::SlidingWindow;
 
 #[macro_export]
 macro_rules! vec_list {
@@ -22,35 +142,73 @@ macro_rules! vec_list {
 }
 
 /// Double Linked List Backed by Vec
-#[derive(Debug, Default, Clone)]
-pub struct VecList<T> {
-    list: Vec<Slot<T>>,
+///
+/// Links and values live in two parallel arrays of the `Storage`, two `Vec`s by default,
+/// so following links never drags the values through the cache.
+///
+/// Since 0.2 the list drops its values in its own `Drop`, so borrowed values must strictly
+/// outlive it, which the `Vec` of slots in 0.1 didn't ask for. A borrow declared after the list
+/// is now rejected:
+///
+/// ```compile_fail,E0597
+/// use vec_list::VecList;
+///
+/// let mut list = VecList::new();
+/// let x = 5;
+/// list.push_back(&x);
+/// ```
+///
+/// Declare the borrowed values first instead:
+///
+/// ```
+/// use vec_list::VecList;
+///
+/// let x = 5;
+/// let mut list = VecList::new();
+/// list.push_back(&x);
+/// ```
+pub struct VecList<T, S: Storage<T> = VecStorage<T>> {
+    /* a value is initialized iff the link at the same index has value */
+    storage: S,
     head: Option<usize>,
     tail: Option<usize>,
+    /* the deleted chain goes from `deleted_tail`, reused first, back to `deleted_head` */
     deleted_tail: Option<usize>,
+    deleted_head: Option<usize>,
+    reuse: SlotReuse,
     len: usize,
+    /* slots `0..len` hold the values in list order and none is deleted */
+    contiguous: bool,
+    /* a bit per slot, set iff it has a value, only if tracking is on */
+    occupied: Option<Vec<u64>>,
+    /* a bit per slot, set iff it's marked for delete */
+    marked: Vec<u64>,
+    /* the slots in list order as a balanced tree, only if indexing is on */
+    positions: Option<Box<positions::PositionIndex>>,
+    /* owns the values in `storage` */
+    _marker: PhantomData<T>,
 }
 
-#[derive(Debug, Clone)]
-enum Slot<T> {
-    Value {
-        val: T,
-        next: Option<usize>,
-        prev: Option<usize>,
-    },
-    Deleted {
-        prev: Option<usize>,
-    },
+/// A `VecList` on `N` inline slots, it never allocates
+pub type ArrayVecList<T, const N: usize> = VecList<T, ArrayStorage<T, N>>;
+
+/// Which deleted slot `push_*` and `insert_*` take first
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SlotReuse {
+    /// The latest deleted one, it's likely still in cache
+    #[default]
+    Lifo,
+    /// The earliest deleted one, so indices come back in the order they were freed
+    Fifo,
 }
 
-impl<T> Slot<T> {
-    fn is_deleted(&self) -> bool {
-        matches!(self, Self::Deleted { .. })
-    }
-
-    fn has_value(&self) -> bool {
-        matches!(self, Self::Value { .. })
-    }
+/// What `VecList::visit_mut` does with the visited element
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Visit {
+    Keep,
+    Remove,
+    /// Keeps it and stops visiting
+    Stop,
 }
 
 impl<T: Clone> VecList<T> {
@@ -63,484 +221,1579 @@ impl<T: Clone> VecList<T> {
     }
 }
 
-impl<T> VecList<T> {
-    pub const fn new() -> Self {
+impl<T: Ord> VecList<T> {
+    /// O(n log k), merges `k` sorted lists into one sorted list through a heap of their fronts,
+    /// equal ones keep the order of `lists`, the values are moved and get new indices
+    pub fn merge_k(lists: Vec<VecList<T>>) -> VecList<T> {
+        let mut merged = Self::with_capacity(lists.iter().map(VecList::len).sum());
+        let mut lists: Vec<_> = lists.into_iter().map(VecList::into_iter).collect();
+        let mut fronts: BinaryHeap<_> = lists
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(from, list)| Some(Reverse((list.next()?, from))))
+            .collect();
+
+        while let Some(Reverse((val, from))) = fronts.pop() {
+            merged.push_back(val);
+            if let Some(next) = lists[from].next() {
+                fronts.push(Reverse((next, from)));
+            }
+        }
+
+        merged
+    }
+}
+
+impl<T: Clone, S: Storage<T>> VecList<T, S> {
+    /// O(k), copies the values from `from` to `to` (inclusive) into a new list, keeping their order.
+    /// Panics if `to` is not `from` or after it.
+    pub fn clone_range(&self, from: usize, to: usize) -> Self {
+        self.assert_range(from, to);
+
+        let mut cloned = Self::default();
+        let mut cur = Some(from);
+        while let Some(idx) = cur {
+            cloned.push_back(unsafe { self.val(idx) }.clone());
+            cur = if idx == to { None } else { self.next(idx) };
+        }
+
+        cloned
+    }
+}
+
+impl<T: Copy, S: Storage<T>> VecList<T, S> {
+    /// O(n), copies `vals` to the back with one memcpy and links the new slots by their indices,
+    /// deleted slots are still reused first like `extend`
+    pub fn extend_from_slice(&mut self, vals: &[T]) {
+        if self.deleted_tail.is_none() {
+            self.storage.reserve(vals.len());
+
+            let spare = self.storage.spare_vals_mut();
+            if spare.len() >= vals.len() {
+                unsafe {
+                    ptr::copy_nonoverlapping(
+                        vals.as_ptr(),
+                        spare.as_mut_ptr().cast::<T>(),
+                        vals.len(),
+                    );
+                    self.commit_spare(vals.len());
+                }
+                return;
+            }
+        }
+
+        self.extend(vals.iter().copied());
+    }
+
+    /// O(cap), like `clone`, but the value slots are copied with one memcpy, deleted ones included,
+    /// `clone` itself can't tell a `Copy` value apart on stable
+    pub fn clone_copy(&self) -> Self {
+        let cap = self.cap();
+        let mut storage = S::with_capacity(cap);
+        storage.reserve(cap);
+
+        let spare = storage.spare_vals_mut();
+        if spare.len() < cap {
+            return self.clone();
+        }
+
+        unsafe {
+            /* a deleted slot copies uninit bytes into a `MaybeUninit`, which is fine */
+            ptr::copy_nonoverlapping(self.storage.vals().as_ptr(), spare.as_mut_ptr(), cap);
+            for link in self.storage.links() {
+                storage.push_link(*link);
+            }
+        }
+
         Self {
-            list: Vec::new(),
-            len: 0,
-            head: None,
-            tail: None,
-            deleted_tail: None,
+            storage,
+            head: self.head,
+            tail: self.tail,
+            deleted_tail: self.deleted_tail,
+            deleted_head: self.deleted_head,
+            reuse: self.reuse,
+            len: self.len,
+            contiguous: self.contiguous,
+            occupied: self.occupied.clone(),
+            marked: self.marked.clone(),
+            positions: self.positions.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: PartialEq, S: Storage<T>> VecList<T, S> {
+    /// O(n), a plain slice scan if `is_contiguous`
+    pub fn contains(&self, x: &T) -> bool {
+        match self.contiguous_vals() {
+            Some(vals) => vals.contains(x),
+            None => self.values().any(|val| val == x),
+        }
+    }
+
+    /// O(n), the index of the first one equal to `x`, a plain slice scan if `is_contiguous`
+    pub fn index_of(&self, x: &T) -> Option<usize> {
+        match self.contiguous_vals() {
+            Some(vals) => vals.iter().position(|val| val == x),
+            None => self.iter().find(|(val, _)| *val == x).map(|(_, idx)| idx),
+        }
+    }
+}
+
+impl<T: PartialOrd, S: Storage<T>> VecList<T, S> {
+    /// O(n), in list order, a plain slice check if `is_contiguous`
+    pub fn is_sorted(&self) -> bool {
+        match self.contiguous_vals() {
+            Some(vals) => vals.is_sorted(),
+            None => self.values().is_sorted(),
+        }
+    }
+}
+
+impl<T: Ord, S: Storage<T>> VecList<T, S> {
+    /// O(n), the index of the smallest, the first one of equal ones
+    pub fn min_index(&self) -> Option<usize> {
+        self.iter()
+            .min_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, idx)| idx)
+    }
+
+    /// O(n), the index of the largest, the last one of equal ones
+    pub fn max_index(&self) -> Option<usize> {
+        self.iter()
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, idx)| idx)
+    }
+
+    /// O(n), walks from the back, so it's O(1) when values come in order.
+    /// For a sorted list it keeps it sorted, after the equal ones.
+    pub fn insert_sorted(&mut self, val: T) -> usize {
+        let mut cur = self.tail;
+        while let Some(idx) = cur {
+            if unsafe { self.val(idx) } <= &val {
+                break;
+            }
+            cur = self.previous(idx);
+        }
+
+        match cur {
+            Some(idx) => self.insert_after(idx, val),
+            None => self.push_front(val),
         }
     }
+}
+
+impl<T, const N: usize> ArrayVecList<T, N> {
+    /// Const, so a list can sit in a `static` without lazy init:
+    ///
+    /// ```
+    /// use std::sync::Mutex;
+    /// use vec_list::ArrayVecList;
+    ///
+    /// static EVENTS: Mutex<ArrayVecList<u32, 8>> = Mutex::new(ArrayVecList::new_inline());
+    ///
+    /// EVENTS.lock().unwrap().push_back(1);
+    /// assert_eq!(EVENTS.lock().unwrap().len(), 1);
+    /// ```
+    pub const fn new_inline() -> Self {
+        Self::from_empty(ArrayStorage::new())
+    }
+}
+
+impl<T> VecList<T> {
+    /// Const, `static LIST: Mutex<VecList<T>> = Mutex::new(VecList::new())` needs no lazy init
+    pub const fn new() -> Self {
+        Self::from_empty(VecStorage::new())
+    }
 
     pub fn with_capacity(cap: usize) -> Self {
+        Self::with_storage(VecStorage::with_capacity(cap))
+    }
+
+    pub fn growth(&self) -> Growth {
+        self.storage.growth()
+    }
+
+    /// How the slots grow from the next time they are full on, kept by `clear` but not by `clone`
+    pub fn set_growth(&mut self, growth: Growth) {
+        self.storage.set_growth(growth);
+    }
+
+    /// O(n), drops the values and gives an empty list of `U` on the same allocation,
+    /// the value buffer is reused only if `T` and `U` have the same size and alignment
+    pub fn into_empty<U>(mut self) -> VecList<U> {
+        self.clear();
+        let storage = mem::take(&mut self.storage);
+
+        VecList::with_storage(storage.recycle())
+    }
+}
+
+impl<T, S: Storage<T>> VecList<T, S> {
+    /// Runs on `storage`, whatever it holds is forgotten
+    pub fn with_storage(mut storage: S) -> Self {
+        storage.clear();
+
+        Self::from_empty(storage)
+    }
+
+    /// `storage` must be empty
+    const fn from_empty(storage: S) -> Self {
         Self {
-            list: Vec::with_capacity(cap),
+            storage,
             len: 0,
             head: None,
             tail: None,
             deleted_tail: None,
+            deleted_head: None,
+            reuse: SlotReuse::Lifo,
+            contiguous: true,
+            occupied: None,
+            marked: Vec::new(),
+            positions: None,
+            _marker: PhantomData,
         }
     }
 
     /// Average O(1)
     pub fn push_back(&mut self, val: T) -> usize {
-        let ret = if let Some(deleted_idx) = self.deleted_tail {
-            let old_tail = self.tail;
-            let deleted_slot = unsafe { self.get_slot_mut(deleted_idx) };
-
-            debug_assert!(deleted_slot.is_deleted());
-            let deleted_prev = match deleted_slot {
-                Slot::Deleted { prev } => *prev,
-                _ => unsafe { unreachable_unchecked() },
-            };
+        let indexed = self.positions_synced();
+        let old_tail = self.tail;
+        let idx = self.alloc(val, Link::value(None, old_tail));
+
+        /* link old tail's next to new element */
+        match old_tail {
+            Some(old_tail) => unsafe { self.set_next(old_tail, Some(idx)) },
+            None => self.head = Some(idx),
+        }
 
-            *deleted_slot = Slot::Value {
-                val,
-                next: None,
-                prev: old_tail,
-            };
+        self.tail = Some(idx);
+        self.len += 1;
+        if indexed {
+            self.positions_inserted(idx, old_tail, None);
+        }
+        idx
+    }
 
-            /* link old tail's next to new element */
-            if let Some(old_tail) = self.tail {
-                let old_tail = unsafe { self.get_slot_mut(old_tail) };
+    /// Average O(1)
+    pub fn push_front(&mut self, val: T) -> usize {
+        let indexed = self.positions_synced();
+        let old_head = self.head;
+        self.contiguous &= old_head.is_none();
+        let idx = self.alloc(val, Link::value(old_head, None));
+
+        /* link old head's prev to new element */
+        match old_head {
+            Some(old_head) => unsafe { self.set_prev(old_head, Some(idx)) },
+            None => self.tail = Some(idx),
+        }
 
-                debug_assert!(old_tail.has_value());
+        self.head = Some(idx);
+        self.len += 1;
+        if indexed {
+            self.positions_inserted(idx, None, old_head);
+        }
+        idx
+    }
+
+    /// O(1), never allocates, gives the value back if there is neither a deleted slot nor spare capacity
+    pub fn push_back_within_capacity(&mut self, val: T) -> Result<usize, T> {
+        if self.has_room() {
+            Ok(self.push_back(val))
+        } else {
+            Err(val)
+        }
+    }
 
-                match old_tail {
-                    Slot::Value { next, .. } => *next = Some(deleted_idx),
-                    _ => unsafe { unreachable_unchecked() },
+    /// O(1), never allocates, gives the value back if there is neither a deleted slot nor spare capacity
+    pub fn push_front_within_capacity(&mut self, val: T) -> Result<usize, T> {
+        if self.has_room() {
+            Ok(self.push_front(val))
+        } else {
+            Err(val)
+        }
+    }
+
+    /// O(1)
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.delete(self.head?)
+    }
+
+    /// O(1)
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.delete(self.tail?)
+    }
+
+    /// O(1), also gives the freed index
+    pub fn pop_front_indexed(&mut self) -> Option<(T, usize)> {
+        let head = self.head?;
+        self.delete(head).map(|val| (val, head))
+    }
+
+    /// O(1), also gives the freed index
+    pub fn pop_back_indexed(&mut self) -> Option<(T, usize)> {
+        let tail = self.tail?;
+        self.delete(tail).map(|val| (val, tail))
+    }
+
+    /// O(1)
+    pub fn front(&self) -> Option<(&T, usize)> {
+        let head = self.head?;
+
+        Some((unsafe { self.val(head) }, head))
+    }
+
+    /// O(1)
+    pub fn back(&self) -> Option<(&T, usize)> {
+        let tail = self.tail?;
+
+        Some((unsafe { self.val(tail) }, tail))
+    }
+
+    /// O(1)
+    pub fn front_mut(&mut self) -> Option<(&mut T, usize)> {
+        let head = self.head?;
+
+        Some((unsafe { self.val_mut(head) }, head))
+    }
+
+    /// O(1)
+    pub fn back_mut(&mut self) -> Option<(&mut T, usize)> {
+        let tail = self.tail?;
+
+        Some((unsafe { self.val_mut(tail) }, tail))
+    }
+
+    /// O(1), `None` if `idx` is deleted or out of range
+    pub fn delete(&mut self, idx: usize) -> Option<T> {
+        let link = *self.storage.links().get(idx)?;
+        if link.is_deleted() {
+            return None;
+        }
+        let indexed = self.positions_synced();
+        let (next, prev) = (link.next(), link.prev());
+
+        /* connect links */
+        unsafe { self.unlink(prev, next) };
+
+        let deleted_val = unsafe { self.storage.vals().get_unchecked(idx).assume_init_read() };
+
+        /* set to empty, and chain it at the end it's reused from */
+        match self.reuse {
+            SlotReuse::Lifo => {
+                unsafe { *self.link_mut(idx) = Link::deleted(self.deleted_tail) };
+                self.deleted_tail = Some(idx);
+                self.deleted_head.get_or_insert(idx);
+            }
+            SlotReuse::Fifo => {
+                unsafe { *self.link_mut(idx) = Link::deleted(None) };
+                match self.deleted_head {
+                    Some(deleted_head) => unsafe {
+                        self.link_mut(deleted_head).set_deleted_prev(Some(idx))
+                    },
+                    None => self.deleted_tail = Some(idx),
                 }
+                self.deleted_head = Some(idx);
             }
+        }
 
-            self.deleted_tail = deleted_prev;
-            self.tail = Some(deleted_idx);
+        self.len -= 1;
+        self.contiguous = false;
+        self.mark_occupied(idx, false);
+        occupancy::set(&mut self.marked, idx, false);
+        if indexed {
+            self.positions_removed(idx);
+        }
 
-            if self.is_empty() {
-                self.head = self.tail;
-            }
+        Some(deleted_val)
+    }
 
-            deleted_idx
+    /// O(1), like `Vec::swap_remove`: the value in the last slot moves into the hole at `idx`
+    /// and the last slot goes away, so the slots stay dense if no other way of deleting is used.
+    /// Also gives the old index of the moved one, whose new index is `idx`.
+    ///
+    /// Deleted slots at the end are dropped from the storage first, walking the deleted chain
+    /// in O(deleted) once, so the last live slot fills the hole all the same.
+    pub fn delete_fill(&mut self, idx: usize) -> Option<(T, Option<usize>)> {
+        let link = *self.storage.links().get(idx)?;
+        if link.is_deleted() {
+            return None;
+        }
+
+        self.trim_deleted();
+        let last = self.cap() - 1;
+
+        unsafe { self.unlink(link.prev(), link.next()) };
+        let val = unsafe { self.storage.vals().get_unchecked(idx).assume_init_read() };
+        self.len -= 1;
+        self.positions_changed();
+
+        let moved = if idx == last {
+            None
         } else {
-            let cur_idx = self.len();
+            let moved_link = unsafe { *self.link(last) };
+            unsafe {
+                let (links, vals) = self.storage.parts_mut();
+                *links.get_unchecked_mut(idx) = moved_link;
+                let moved_val = vals.get_unchecked(last).assume_init_read();
+                vals.get_unchecked_mut(idx).write(moved_val);
+
+                match moved_link.prev() {
+                    Some(prev) => self.set_next(prev, Some(idx)),
+                    None => self.head = Some(idx),
+                }
+                match moved_link.next() {
+                    Some(next) => self.set_prev(next, Some(idx)),
+                    None => self.tail = Some(idx),
+                }
+            }
 
-            self.list.push(Slot::Value {
-                val,
-                next: None,
-                prev: self.tail,
-            });
+            self.contiguous = false;
+            let marked = occupancy::get(&self.marked, last);
+            occupancy::set(&mut self.marked, idx, marked);
+            Some(last)
+        };
 
-            if self.is_empty() {
-                self.head = Some(0);
-            } else {
-                debug_assert!(self.tail.is_some());
-                let old_tail =
-                    unsafe { self.get_slot_mut(unsafe { self.tail.unwrap_unchecked() }) };
-
-                debug_assert!(old_tail.has_value());
-                match old_tail {
-                    Slot::Value { next, .. } => *next = Some(cur_idx),
-                    _ => unsafe { unreachable_unchecked() },
+        /* the last slot was read out or is `idx` itself */
+        self.storage.truncate(last);
+        self.mark_occupied(last, false);
+        occupancy::set(&mut self.marked, last, false);
+        Some((val, moved))
+    }
+
+    /// O(k log k + cap / 64), deletes and drops the values at `indices` in index order, repeated,
+    /// deleted and out of range ones are skipped. Gives how many were deleted.
+    ///
+    /// Each run of neighbors is unlinked at once and the slots join the deleted chain together,
+    /// in the same order as deleting them one by one front to back.
+    pub fn delete_many(&mut self, indices: &[usize]) -> usize {
+        let links = self.storage.links();
+        let mut indices: Vec<usize> = indices
+            .iter()
+            .copied()
+            .filter(|&idx| links.get(idx).is_some_and(Link::has_value))
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+        let (Some(&first), Some(&last)) = (indices.first(), indices.last()) else {
+            return 0;
+        };
+        let indexed = self.positions_synced();
+
+        let mut doomed = Vec::new();
+        for &idx in &indices {
+            occupancy::set(&mut doomed, idx, true);
+        }
+
+        /* connect links around each run, from the one whose previous one stays */
+        for &idx in &indices {
+            let prev = unsafe { self.link(idx) }.prev();
+            if prev.is_some_and(|prev| occupancy::get(&doomed, prev)) {
+                continue;
+            }
+
+            let mut end = idx;
+            while let Some(next) = unsafe { self.link(end) }.next() {
+                if !occupancy::get(&doomed, next) {
+                    break;
+                }
+                end = next;
+            }
+            unsafe { self.unlink(prev, self.link(end).next()) };
+        }
+
+        let deleted_vals: Vec<T> = indices
+            .iter()
+            .map(|&idx| unsafe { self.storage.vals().get_unchecked(idx).assume_init_read() })
+            .collect();
+
+        /* chain them up in index order, then hook the chain on at the end it's reused from */
+        let links = self.storage.parts_mut().0;
+        match self.reuse {
+            SlotReuse::Lifo => {
+                let mut prev = self.deleted_tail;
+                for &idx in &indices {
+                    links[idx] = Link::deleted(prev);
+                    prev = Some(idx);
+                }
+                self.deleted_tail = Some(last);
+                self.deleted_head.get_or_insert(first);
+            }
+            SlotReuse::Fifo => {
+                let mut prev = None;
+                for &idx in indices.iter().rev() {
+                    links[idx] = Link::deleted(prev);
+                    prev = Some(idx);
                 }
+                match self.deleted_head {
+                    Some(deleted_head) => links[deleted_head].set_deleted_prev(Some(first)),
+                    None => self.deleted_tail = Some(first),
+                }
+                self.deleted_head = Some(last);
             }
+        }
 
-            self.tail = Some(cur_idx);
-            cur_idx
+        self.len -= indices.len();
+        self.contiguous = false;
+        if let Some(occupied) = &mut self.occupied {
+            occupancy::clear_all(occupied, &doomed);
+        }
+        occupancy::clear_all(&mut self.marked, &doomed);
+        if indexed {
+            for &idx in &indices {
+                self.positions_removed(idx);
+            }
+        }
+
+        /* the list is whole again, so a panicking drop can only leak the rest */
+        drop(deleted_vals);
+        indices.len()
+    }
+
+    /// Average O(1), the new one is right after `idx`, panics if `idx` is deleted
+    pub fn insert_after(&mut self, idx: usize, val: T) -> usize {
+        assert!(self.get(idx).is_some(), "invalid key!");
+
+        match self.next(idx) {
+            Some(next) => self.insert_between(idx, next, val),
+            None => self.push_back(val),
+        }
+    }
+
+    /// Average O(1), the new one is right before `idx`, panics if `idx` is deleted
+    pub fn insert_before(&mut self, idx: usize, val: T) -> usize {
+        assert!(self.get(idx).is_some(), "invalid key!");
+
+        match self.previous(idx) {
+            Some(prev) => self.insert_between(prev, idx, val),
+            None => self.push_front(val),
+        }
+    }
+
+    /// O(1), swaps where `a` and `b` are in the list, the values and indices stay untouched
+    pub fn swap_links(&mut self, a: usize, b: usize) {
+        assert!(
+            self.get(a).is_some() && self.get(b).is_some(),
+            "invalid key!"
+        );
+
+        if a == b {
+            return;
+        }
+        self.contiguous = false;
+
+        let (a_link, b_link) = unsafe { (*self.link(a), *self.link(b)) };
+        /* if they are adjacent, one's new neighbor is the other one itself */
+        let swapped = |neighbor: Option<usize>, other: usize, this: usize| {
+            if neighbor == Some(this) {
+                Some(other)
+            } else {
+                neighbor
+            }
         };
+        let a_next = swapped(b_link.next(), b, a);
+        let a_prev = swapped(b_link.prev(), b, a);
+        let b_next = swapped(a_link.next(), a, b);
+        let b_prev = swapped(a_link.prev(), a, b);
 
-        self.len += 1;
-        ret
+        unsafe {
+            *self.link_mut(a) = Link::value(a_next, a_prev);
+            *self.link_mut(b) = Link::value(b_next, b_prev);
+
+            for (idx, prev, next) in [(a, a_prev, a_next), (b, b_prev, b_next)] {
+                match prev {
+                    Some(prev) => self.set_next(prev, Some(idx)),
+                    None => self.head = Some(idx),
+                }
+                match next {
+                    Some(next) => self.set_prev(next, Some(idx)),
+                    None => self.tail = Some(idx),
+                }
+            }
+        }
     }
 
-    /// Average O(1)
-    pub fn push_front(&mut self, val: T) -> usize {
-        let ret = if let Some(deleted_idx) = self.deleted_tail {
-            let old_head = self.head;
-            let deleted_slot = unsafe { self.get_slot_mut(deleted_idx) };
+    /// O(k), moves the nodes from `from` to `to` (inclusive) into a new list, keeping their order.
+    /// Panics if `to` is not `from` or after it.
+    pub fn cut_range(&mut self, from: usize, to: usize) -> Self {
+        self.assert_range(from, to);
+
+        let mut cut = Self::default();
+        let mut cur = from;
+        loop {
+            let next = self.next(cur);
+            if let Some(val) = self.delete(cur) {
+                cut.push_back(val);
+            }
+
+            match next {
+                Some(next) if cur != to => cur = next,
+                _ => break,
+            }
+        }
 
-            debug_assert!(deleted_slot.is_deleted());
+        cut
+    }
 
-            let deleted_prev = match deleted_slot {
-                Slot::Deleted { prev } => *prev,
-                _ => unsafe { unreachable_unchecked() },
+    /// O(m), moves the elements of `other` in between the ones of `self`, alternating from
+    /// the front of `self`, and the rest of the longer one ends up at the back.
+    /// The ones of `self` keep their indices.
+    pub fn interleave<O: Storage<T>>(&mut self, other: VecList<T, O>) {
+        let mut cur = self.head;
+        for val in other {
+            cur = match cur {
+                Some(idx) => {
+                    let idx = self.insert_after(idx, val);
+                    self.next(idx)
+                }
+                None => {
+                    self.push_back(val);
+                    None
+                }
             };
+        }
+    }
+
+    /// O(n), moves the elements into `n` lists in one walk, keeping their order,
+    /// lengths differ by one at most, the longer ones come first.
+    /// Panics if `n` is 0.
+    pub fn split_n(self, n: usize) -> Vec<Self> {
+        assert!(n > 0, "n can't be zero!");
+
+        let (size, longer) = (self.len / n, self.len % n);
+        let mut vals = self.into_iter();
+        (0..n)
+            .map(|piece| {
+                let mut list = Self::with_storage(S::with_capacity(0));
+                list.extend(vals.by_ref().take(size + usize::from(piece < longer)));
+                list
+            })
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn cap(&self) -> usize {
+        self.storage.links().len()
+    }
+
+    pub fn vec_cap(&self) -> usize {
+        self.storage.capacity()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Makes room for at least `additional` more slots, a hint for fixed storages
+    pub fn reserve(&mut self, additional: usize) {
+        self.storage.reserve(additional);
+    }
+
+    /// Like `reserve`, but fails with `CapacityExceeded` instead of panicking or aborting
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), VecListError> {
+        if self.cap().saturating_add(additional) > link::MAX_SLOTS {
+            return Err(VecListError::CapacityExceeded);
+        }
+
+        self.storage.try_reserve(additional)
+    }
 
-            *deleted_slot = Slot::Value {
-                val,
-                next: old_head,
-                prev: None,
+    /// The slots past the last one, up to the capacity, for writing values in place.
+    /// `commit_spare` links the written ones.
+    pub fn spare_slots_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        self.storage.spare_vals_mut()
+    }
+
+    /// O(n), appends the first `n` of `spare_slots_mut` to the back in order, gives their indices
+    ///
+    /// # Safety
+    /// `n` must be at most the spare slot count, and the first `n` spare slots must be initialized.
+    pub unsafe fn commit_spare(&mut self, n: usize) -> ops::Range<usize> {
+        let start = self.cap();
+        if n == 0 {
+            return start..start;
+        }
+        assert!(n < link::MAX_SLOTS - start, "too many slots!");
+        debug_assert!(n <= self.storage.spare_vals_mut().len());
+
+        for idx in start..start + n {
+            let prev = if idx == start {
+                self.tail
+            } else {
+                Some(idx - 1)
             };
+            let next = if idx + 1 == start + n {
+                None
+            } else {
+                Some(idx + 1)
+            };
+            self.storage.push_link(Link::value(next, prev));
+        }
 
-            /* link old head's next to new element */
-            if let Some(old_head) = self.head {
-                let old_tail = unsafe { self.get_slot_mut(old_head) };
+        match self.tail {
+            Some(old_tail) => self.set_next(old_tail, Some(start)),
+            None => self.head = Some(start),
+        }
+        self.tail = Some(start + n - 1);
+        self.len += n;
+        self.positions_changed();
+        for idx in start..start + n {
+            self.mark_occupied(idx, true);
+        }
 
-                debug_assert!(old_tail.has_value());
-                match old_tail {
-                    Slot::Value { prev, .. } => *prev = Some(deleted_idx),
-                    _ => unsafe { unreachable_unchecked() },
-                }
+        start..start + n
+    }
+
+    /// Frees the spare capacity, deleted slots still count,
+    /// `optimize_for_iteration` first to get rid of them too
+    pub fn shrink_to_fit(&mut self) {
+        self.storage.shrink_to(0);
+    }
+
+    /// Frees the spare capacity above `min_capacity`, keeping at least the slots in use
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.storage.shrink_to(min_capacity);
+    }
+
+    /// O(1), whether slots `0..len` hold the values in list order with no deleted slot,
+    /// as after `optimize_for_iteration` and any run of `push_back`s on top of it
+    pub fn is_contiguous(&self) -> bool {
+        self.contiguous
+    }
+
+    pub fn slot_reuse(&self) -> SlotReuse {
+        self.reuse
+    }
+
+    /// O(1), for the slots deleted from now on, earlier ones keep their place in the chain
+    pub fn set_slot_reuse(&mut self, reuse: SlotReuse) {
+        self.reuse = reuse;
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            links: self.storage.links(),
+            vals: self.storage.vals(),
+            prev: self.tail,
+            next: self.head,
+            len: self.len,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (head, tail, len) = (self.head, self.tail, self.len);
+        let (links, vals) = self.storage.parts_mut();
+
+        IterMut {
+            links,
+            vals: vals.as_mut_ptr(),
+            next: head,
+            prev: tail,
+            len,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Like `iter`, but without indices
+    pub fn values(&self) -> Values<'_, T> {
+        Values { iter: self.iter() }
+    }
+
+    /// Like `iter_mut`, but without indices
+    pub fn values_mut(&mut self) -> ValuesMut<'_, T> {
+        ValuesMut {
+            iter: self.iter_mut(),
+        }
+    }
+
+    /// Maximal runs of consecutive elements where `pred` holds for every two neighbors,
+    /// each one as an iterator with the indices of its first and last elements
+    pub fn chunk_by<F: FnMut(&T, &T) -> bool>(&self, pred: F) -> ChunkBy<'_, T, F> {
+        ChunkBy {
+            iter: self.iter(),
+            pending: None,
+            pred,
+        }
+    }
+
+    /// Values of `self` and then values of `other`, nothing is moved
+    pub fn chain<'a>(&'a self, other: &'a VecList<T, S>) -> Chain<Values<'a, T>, Values<'a, T>> {
+        self.values().chain(other.values())
+    }
+
+    /// Values of `self` and then values of `other`, nothing is moved
+    pub fn chain_mut<'a>(
+        &'a mut self,
+        other: &'a mut VecList<T, S>,
+    ) -> Chain<ValuesMut<'a, T>, ValuesMut<'a, T>> {
+        self.values_mut().chain(other.values_mut())
+    }
+
+    /// # Safety
+    /// Must in range && Must not deleted
+    pub unsafe fn get_unchecked(&self, idx: usize) -> &T {
+        self.val(idx)
+    }
+
+    /// # Safety
+    /// Must in range && Must not deleted
+    pub unsafe fn get_unchecked_mut(&mut self, idx: usize) -> &mut T {
+        self.val_mut(idx)
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        match self.storage.links().get(idx) {
+            Some(link) if link.has_value() => Some(unsafe { self.val(idx) }),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        match self.storage.links().get(idx) {
+            Some(link) if link.has_value() => Some(unsafe { self.val_mut(idx) }),
+            _ => None,
+        }
+    }
+
+    pub fn next(&self, idx: usize) -> Option<usize> {
+        self.storage.links().get(idx)?.next()
+    }
+
+    pub fn previous(&self, idx: usize) -> Option<usize> {
+        self.storage.links().get(idx)?.prev()
+    }
+
+    /// O(n), like `min_index` by `f` of the values
+    pub fn min_index_by_key<K: Ord, F: FnMut(&T) -> K>(&self, mut f: F) -> Option<usize> {
+        self.iter()
+            .map(|(val, idx)| (f(val), idx))
+            .min_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, idx)| idx)
+    }
+
+    /// O(n), like `max_index` by `f` of the values
+    pub fn max_index_by_key<K: Ord, F: FnMut(&T) -> K>(&self, mut f: F) -> Option<usize> {
+        self.iter()
+            .map(|(val, idx)| (f(val), idx))
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, idx)| idx)
+    }
+
+    /// O(n), whether `f` holds for every two neighbors in list order
+    pub fn is_sorted_by<F: FnMut(&T, &T) -> bool>(&self, mut f: F) -> bool {
+        self.values().is_sorted_by(|a, b| f(a, b))
+    }
+
+    /// O(n), whether `f` of the values is sorted in list order
+    pub fn is_sorted_by_key<K: PartialOrd, F: FnMut(&T) -> K>(&self, f: F) -> bool {
+        self.values().map(f).is_sorted()
+    }
+
+    /// O(1), the values before and after `idx`, panics if `idx` is deleted
+    pub fn neighbors(&self, idx: usize) -> (Option<&T>, Option<&T>) {
+        assert!(self.get(idx).is_some(), "invalid key!");
+
+        let link = unsafe { self.link(idx) };
+        (
+            link.prev().map(|prev| unsafe { self.val(prev) }),
+            link.next().map(|next| unsafe { self.val(next) }),
+        )
+    }
+
+    /// O(1), like `neighbors`, the two are never the same slot
+    pub fn neighbors_mut(&mut self, idx: usize) -> (Option<&mut T>, Option<&mut T>) {
+        assert!(self.get(idx).is_some(), "invalid key!");
+
+        let link = unsafe { *self.link(idx) };
+        let vals = self.storage.parts_mut().1.as_mut_ptr();
+        /* `prev != next`, so the references don't overlap */
+        unsafe {
+            (
+                link.prev().map(|prev| (*vals.add(prev)).assume_init_mut()),
+                link.next().map(|next| (*vals.add(next)).assume_init_mut()),
+            )
+        }
+    }
+
+    pub fn clear(&mut self) {
+        /* forgets the slots even if a drop panics, so the rest leak instead of double dropping */
+        struct Forget<'a, T, S: Storage<T>>(&'a mut S, PhantomData<T>);
+
+        impl<'a, T, S: Storage<T>> Drop for Forget<'a, T, S> {
+            fn drop(&mut self) {
+                self.0.clear();
             }
+        }
 
-            self.deleted_tail = deleted_prev;
-            self.head = Some(deleted_idx);
+        self.len = 0;
+        self.head = None;
+        self.tail = None;
+        self.deleted_tail = None;
+        self.deleted_head = None;
+        self.contiguous = true;
+        if let Some(occupied) = &mut self.occupied {
+            occupied.clear();
+        }
+        self.marked.clear();
+        if self.positions.is_some() {
+            self.set_position_index(true);
+        }
 
-            if self.is_empty() {
-                self.tail = self.head;
+        let forget = Forget(&mut self.storage, PhantomData);
+        let (links, vals) = forget.0.parts_mut();
+        for (link, val) in links.iter().zip(vals.iter_mut()) {
+            if link.has_value() {
+                unsafe { val.assume_init_drop() };
             }
+        }
+    }
 
-            deleted_idx
-        } else {
-            let cur_idx = self.len();
+    /// O(n), keeps the order
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let mut cur = self.head;
 
-            self.list.push(Slot::Value {
-                val,
-                next: self.head,
-                prev: None,
-            });
+        while let Some(idx) = cur {
+            cur = self.next(idx);
 
-            if self.is_empty() {
-                self.tail = Some(0);
-            } else {
-                debug_assert!(self.head.is_some());
-                let old_head =
-                    unsafe { self.get_slot_mut(unsafe { self.head.unwrap_unchecked() }) };
-                debug_assert!(old_head.has_value());
-
-                match old_head {
-                    Slot::Value { prev, .. } => *prev = Some(cur_idx),
-                    _ => unsafe { unreachable_unchecked() },
+            if !f(unsafe { self.val(idx) }) {
+                self.delete(idx);
+            }
+        }
+    }
+
+    /// O(n), keeps the order, `f` gets the index too
+    pub fn retain_indexed<F: FnMut(usize, &mut T) -> bool>(&mut self, mut f: F) {
+        let mut cur = self.head;
+
+        while let Some(idx) = cur {
+            cur = self.next(idx);
+
+            if !f(idx, unsafe { self.val_mut(idx) }) {
+                self.delete(idx);
+            }
+        }
+    }
+
+    /// O(n) at most, from front to back, the removed ones are dropped
+    pub fn visit_mut<F: FnMut(&mut T) -> Visit>(&mut self, f: F) {
+        self.visit_mut_into(f, drop);
+    }
+
+    /// O(n) at most, from front to back, the removed ones are given to `sink` with their old indices
+    pub fn visit_mut_into<F, K>(&mut self, mut f: F, mut sink: K)
+    where
+        F: FnMut(&mut T) -> Visit,
+        K: FnMut((T, usize)),
+    {
+        let mut cur = self.head;
+
+        while let Some(idx) = cur {
+            cur = self.next(idx);
+
+            match f(unsafe { self.val_mut(idx) }) {
+                Visit::Keep => {}
+                Visit::Remove => {
+                    if let Some(val) = self.delete(idx) {
+                        sink((val, idx));
+                    }
                 }
+                Visit::Stop => break,
             }
+        }
+    }
 
-            self.head = Some(cur_idx);
-            cur_idx
-        };
+    /// O(n), moves the values so that the slot order is the same as the list order,
+    /// and deleted slots are dropped, so iteration turns into a linear scan.
+    ///
+    /// Every index changes, the returned remap maps an old index to its new one,
+    /// or to `None` if the old slot was deleted.
+    pub fn optimize_for_iteration(&mut self) -> Vec<Option<usize>> {
+        let mut remap = vec![None; self.cap()];
+        let mut vals = Vec::with_capacity(self.len);
+
+        let mut cur = self.head;
+        while let Some(idx) = cur {
+            cur = unsafe { self.link(idx) }.next();
+            remap[idx] = Some(vals.len());
+            vals.push(unsafe { self.storage.vals().get_unchecked(idx).assume_init_read() });
+        }
 
-        self.len += 1;
-        ret
+        debug_assert_eq!(vals.len(), self.len);
+        /* the values are moved out, the slots are forgotten without dropping them */
+        self.storage.clear();
+        for (new_idx, val) in vals.into_iter().enumerate() {
+            let next = if new_idx + 1 < self.len {
+                Some(new_idx + 1)
+            } else {
+                None
+            };
+
+            self.storage.push(
+                Link::value(next, new_idx.checked_sub(1)),
+                MaybeUninit::new(val),
+            );
+        }
+        self.head = if self.len > 0 { Some(0) } else { None };
+        self.tail = self.len.checked_sub(1);
+        self.deleted_tail = None;
+        self.deleted_head = None;
+        self.contiguous = true;
+        if self.occupied.is_some() {
+            self.set_occupancy_tracking(true);
+        }
+        if self.positions.is_some() {
+            self.set_position_index(true);
+        }
+        let marked = mem::take(&mut self.marked);
+        for old_idx in occupancy::ones(&marked) {
+            if let Some(new_idx) = remap[old_idx] {
+                occupancy::set(&mut self.marked, new_idx, true);
+            }
+        }
+
+        remap
     }
 
-    /// O(1)
-    pub fn pop_front(&mut self) -> Option<T> {
-        self.delete(self.head?)
+    /// Indices of the deleted slots, the one `push_*` reuses next comes first
+    #[cfg(feature = "diagnostics")]
+    pub fn free_slots(&self) -> FreeSlots<'_> {
+        FreeSlots {
+            links: self.storage.links(),
+            next: self.deleted_tail,
+        }
     }
 
-    /// O(1)
-    pub fn pop_back(&mut self) -> Option<T> {
-        self.delete(self.tail?)
+    /// Removes all elements from front to back, the rest are dropped if the `Drain` is dropped early
+    pub fn drain(&mut self) -> Drain<'_, T, S> {
+        Drain { list: self }
     }
 
-    /// O(1)
-    pub fn front(&self) -> Option<(&T, usize)> {
-        unsafe {
-            debug_assert!(self.get_slot(self.head?).has_value());
-            let head = self.head?;
+    /// O(k), walks from `from` to `to`
+    fn assert_range(&self, from: usize, to: usize) {
+        assert!(
+            self.get(from).is_some() && self.get(to).is_some(),
+            "invalid key!"
+        );
 
-            Some((
-                match self.get_slot(head) {
-                    Slot::Value { val, .. } => val,
-                    _ => unreachable_unchecked(),
-                },
-                head,
-            ))
+        let mut cur = from;
+        while cur != to {
+            cur = self.next(cur).expect("`to` is before `from`!");
         }
     }
 
-    /// O(1)
-    pub fn back(&self) -> Option<(&T, usize)> {
-        unsafe {
-            debug_assert!(self.get_slot(self.tail?).has_value());
-            let tail = self.tail?;
-
-            Some((
-                match self.get_slot(self.tail?) {
-                    Slot::Value { val, .. } => val,
-                    _ => unreachable_unchecked(),
-                },
-                tail,
-            ))
+    /// The values in list order, if they are contiguous
+    fn contiguous_vals(&self) -> Option<&[T]> {
+        if !self.contiguous {
+            return None;
         }
+
+        let vals = self.storage.vals();
+        debug_assert_eq!(vals.len(), self.len);
+        Some(unsafe { &*(vals as *const [MaybeUninit<T>] as *const [T]) })
     }
 
-    /// O(1)
-    pub fn front_mut(&mut self) -> Option<(&mut T, usize)> {
+    /// Whether `alloc` can succeed without allocating
+    fn has_room(&self) -> bool {
+        self.deleted_tail.is_some() || self.cap() < self.storage.capacity()
+    }
+
+    /// Average O(1), `prev` and `next` must be neighbors
+    fn insert_between(&mut self, prev: usize, next: usize, val: T) -> usize {
+        let indexed = self.positions_synced();
+        let idx = self.alloc(val, Link::value(Some(next), Some(prev)));
         unsafe {
-            debug_assert!(self.get_slot(self.head?).has_value());
-            let head = self.head?;
+            self.set_next(prev, Some(idx));
+            self.set_prev(next, Some(idx));
+        }
 
-            Some((
-                match self.get_slot_mut(head) {
-                    Slot::Value { val, .. } => val,
-                    _ => unreachable_unchecked(),
-                },
-                head,
-            ))
+        self.len += 1;
+        self.contiguous = false;
+        if indexed {
+            self.positions_inserted(idx, Some(prev), Some(next));
         }
+        idx
     }
 
-    /// O(1)
-    pub fn back_mut(&mut self) -> Option<(&mut T, usize)> {
-        unsafe {
-            debug_assert!(self.get_slot(self.tail?).has_value());
-            let tail = self.tail?;
+    /// O(deleted) if the last slot is deleted, drops the deleted slots at the end of the storage
+    /// and takes them off the deleted chain, keeping the order of the others
+    fn trim_deleted(&mut self) {
+        let mut keep = self.cap();
+        while keep > 0 && unsafe { self.link(keep - 1) }.is_deleted() {
+            keep -= 1;
+        }
+        if keep == self.cap() {
+            return;
+        }
 
-            Some((
-                match self.get_slot_mut(self.tail?) {
-                    Slot::Value { val, .. } => val,
-                    _ => unreachable_unchecked(),
-                },
-                tail,
-            ))
+        /* from the tail of the chain, `kept` is the last one staying */
+        let (mut cur, mut kept) = (self.deleted_tail, None);
+        self.deleted_tail = None;
+        while let Some(idx) = cur {
+            cur = unsafe { self.link(idx) }.deleted_prev();
+            if idx >= keep {
+                continue;
+            }
+
+            match kept {
+                Some(kept) => unsafe { self.link_mut(kept).set_deleted_prev(Some(idx)) },
+                None => self.deleted_tail = Some(idx),
+            }
+            kept = Some(idx);
+        }
+        if let Some(kept) = kept {
+            unsafe { self.link_mut(kept).set_deleted_prev(None) };
         }
+        self.deleted_head = kept;
+
+        /* the slots are deleted, nothing to drop */
+        self.storage.truncate(keep);
     }
 
-    /// O(1)
-    pub fn delete(&mut self, idx: usize) -> Option<T> {
-        assert!(idx < self.cap());
+    /// Takes a deleted slot or grows the storage, neighbors are left to the caller
+    fn alloc(&mut self, val: T, link: Link) -> usize {
+        if let Some(deleted_idx) = self.deleted_tail {
+            let deleted_link = unsafe { self.link_mut(deleted_idx) };
+
+            debug_assert!(deleted_link.is_deleted());
+            let deleted_prev = deleted_link.deleted_prev();
+
+            *deleted_link = link;
+            unsafe {
+                self.storage
+                    .parts_mut()
+                    .1
+                    .get_unchecked_mut(deleted_idx)
+                    .write(val)
+            };
+            self.deleted_tail = deleted_prev;
+            if deleted_prev.is_none() {
+                self.deleted_head = None;
+            }
+            self.mark_occupied(deleted_idx, true);
+
+            deleted_idx
+        } else {
+            assert!(self.cap() < link::MAX_SLOTS, "too many slots!");
 
-        let old_delete_head = self.deleted_tail;
-        let to_delete = unsafe { self.get_slot_mut(idx) } as *mut Slot<T>;
+            self.storage.push(link, MaybeUninit::new(val));
+            self.mark_occupied(self.cap() - 1, true);
 
-        /* connect links */
-        let deleted_val = match unsafe { &mut *to_delete } {
-            Slot::Value { next, prev, val } => {
-                let to_delete_next = *next;
-                let to_delete_prev = *prev;
-
-                debug_assert!(self.head.is_some());
-                debug_assert!(self.tail.is_some());
-                /* solve head && tail */
-                if unsafe { self.head.unwrap_unchecked() } == idx {
-                    self.head = to_delete_next;
-                }
-                if unsafe { self.tail.unwrap_unchecked() } == idx {
-                    self.tail = to_delete_prev;
-                }
+            self.cap() - 1
+        }
+    }
 
-                /* solve previous */
-                if let Some(prev) = to_delete_prev {
-                    let prev = unsafe { self.get_slot_mut(prev) };
+    /// Appends `n` fresh slots, each one is linked to the next one while being written,
+    /// and the whole run is stitched onto the tail at the end, even if `iter` panics
+    fn extend_run<I: Iterator<Item = T>>(&mut self, iter: I, n: usize) {
+        struct Run<'a, T, S: Storage<T>> {
+            list: &'a mut VecList<T, S>,
+            start: usize,
+        }
 
-                    debug_assert!(prev.has_value());
-                    match prev {
-                        Slot::Value { next, .. } => {
-                            *next = to_delete_next;
-                        }
-                        Slot::Deleted { .. } => unsafe { unreachable_unchecked() },
-                    }
+        impl<'a, T, S: Storage<T>> Drop for Run<'a, T, S> {
+            fn drop(&mut self) {
+                let list = &mut *self.list;
+                let added = list.cap() - self.start;
+                if added == 0 {
+                    return;
                 }
 
-                /* solve next */
-                if let Some(next) = to_delete_next {
-                    let next = unsafe { self.get_slot_mut(next) };
+                let last = list.cap() - 1;
+                unsafe { list.link_mut(last).set_next(None) };
 
-                    debug_assert!(next.has_value());
-                    match next {
-                        Slot::Value { prev, .. } => {
-                            *prev = to_delete_prev;
-                        }
-                        Slot::Deleted { .. } => unsafe { unreachable_unchecked() },
-                    }
+                match list.tail {
+                    Some(old_tail) => unsafe { list.set_next(old_tail, Some(self.start)) },
+                    None => list.head = Some(self.start),
                 }
 
-                unsafe { ptr::read(val) }
+                list.tail = Some(last);
+                list.len += added;
+                list.positions_changed();
+                for idx in self.start..=last {
+                    list.mark_occupied(idx, true);
+                }
             }
-            Slot::Deleted { .. } => return None,
-        };
+        }
 
-        /* set to empty */
-        unsafe {
-            ptr::write(
-                to_delete,
-                Slot::Deleted {
-                    prev: old_delete_head,
-                },
-            );
+        debug_assert!(self.deleted_tail.is_none());
+        assert!(n < link::MAX_SLOTS - self.cap(), "too many slots!");
+
+        self.storage.reserve(n);
+
+        let start = self.cap();
+        let old_tail = self.tail;
+        let run = Run { list: self, start };
+
+        for (i, val) in iter.take(n).enumerate() {
+            let idx = start + i;
+            let prev = if i == 0 { old_tail } else { Some(idx - 1) };
+
+            run.list
+                .storage
+                .push(Link::value(Some(idx + 1), prev), MaybeUninit::new(val));
         }
+    }
 
-        self.deleted_tail = Some(idx);
-        self.len -= 1;
+    // SAFETY: `prev` and `next` must be the neighbors of a node which is being removed
+    unsafe fn unlink(&mut self, prev: Option<usize>, next: Option<usize>) {
+        /* solve previous */
+        match prev {
+            Some(prev) => self.set_next(prev, next),
+            None => self.head = next,
+        }
 
-        Some(deleted_val)
+        /* solve next */
+        match next {
+            Some(next) => self.set_prev(next, prev),
+            None => self.tail = prev,
+        }
     }
 
-    pub fn len(&self) -> usize {
-        self.len
+    // SAFETY: Must in range && Must not deleted
+    unsafe fn set_next(&mut self, idx: usize, new_next: Option<usize>) {
+        self.link_mut(idx).set_next(new_next);
     }
 
-    pub fn cap(&self) -> usize {
-        self.list.len()
+    // SAFETY: Must in range && Must not deleted
+    unsafe fn set_prev(&mut self, idx: usize, new_prev: Option<usize>) {
+        self.link_mut(idx).set_prev(new_prev);
     }
 
-    pub fn vec_cap(&self) -> usize {
-        self.list.capacity()
+    // SAFETY: Must in range && Must not deleted
+    unsafe fn val(&self, idx: usize) -> &T {
+        debug_assert!(self.link(idx).has_value());
+
+        self.storage.vals().get_unchecked(idx).assume_init_ref()
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
+    // SAFETY: Must in range && Must not deleted
+    unsafe fn val_mut(&mut self, idx: usize) -> &mut T {
+        debug_assert!(self.link(idx).has_value());
+
+        self.storage
+            .parts_mut()
+            .1
+            .get_unchecked_mut(idx)
+            .assume_init_mut()
     }
 
-    pub fn iter(&self) -> Iter<T> {
-        Iter {
-            list: self,
-            prev: self.tail,
-            next: self.head,
-        }
+    // SAFETY: Must in range
+    unsafe fn link_mut(&mut self, idx: usize) -> &mut Link {
+        debug_assert!(idx < self.cap());
+        self.positions_changed();
+
+        self.storage.parts_mut().0.get_unchecked_mut(idx)
     }
 
-    pub fn iter_mut(&mut self) -> IterMut<T> {
-        let next = self.head;
-        let prev = self.tail;
+    // SAFETY: Must in range
+    unsafe fn link(&self, idx: usize) -> &Link {
+        debug_assert!(idx < self.cap());
 
-        IterMut {
-            list: self,
-            next,
-            prev,
-        }
+        self.storage.links().get_unchecked(idx)
     }
+}
 
-    pub fn into_iter(self) -> IntoIter<T> {
-        IntoIter { list: self }
+impl<T, S: Storage<T>> Drop for VecList<T, S> {
+    fn drop(&mut self) {
+        self.clear();
     }
+}
 
-    /// # Safety
-    /// Must in range && Must not deleted
-    pub unsafe fn get_unchecked(&self, idx: usize) -> &T {
-        debug_assert!(self.get_slot(idx).has_value());
+/// Iterators with an exact size are written in one pass when there is no deleted slot to reuse
+impl<T, S: Storage<T>> Extend<T> for VecList<T, S> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut iter = iter.into_iter();
 
-        match self.get_slot(idx) {
-            Slot::Value { val, .. } => val,
-            _ => unreachable_unchecked(),
+        if let (lower, Some(upper)) = iter.size_hint() {
+            if lower == upper && self.deleted_tail.is_none() {
+                self.extend_run(iter.by_ref(), lower);
+            }
         }
-    }
-
-    /// # Safety
-    /// Must in range && Must not deleted
-    pub unsafe fn get_unchecked_mut(&mut self, idx: usize) -> &mut T {
-        debug_assert!(self.get_slot(idx).has_value());
 
-        match self.get_slot_mut(idx) {
-            Slot::Value { val, .. } => val,
-            _ => unreachable_unchecked(),
+        for val in iter {
+            self.push_back(val);
         }
     }
+}
 
-    pub fn get(&self, idx: usize) -> Option<&T> {
-        match self.list.get(idx) {
-            Some(Slot::Value { val, .. }) => Some(val),
-            _ => None,
-        }
+impl<T, S: Storage<T>> FromIterator<T> for VecList<T, S> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::default();
+        list.extend(iter);
+        list
     }
+}
 
-    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
-        match self.list.get_mut(idx) {
-            Some(Slot::Value { val, .. }) => Some(val),
-            _ => None,
-        }
+impl<T, S: Storage<T>> Default for VecList<T, S> {
+    fn default() -> Self {
+        Self::with_storage(S::with_capacity(0))
     }
+}
 
-    pub fn next(&self, idx: usize) -> Option<usize> {
-        if idx < self.cap() {
-            return None;
+impl<T: Clone, S: Storage<T>> Clone for VecList<T, S> {
+    fn clone(&self) -> Self {
+        let mut storage = S::with_capacity(self.cap());
+        for (idx, link) in self.storage.links().iter().enumerate() {
+            storage.push(
+                *link,
+                if link.has_value() {
+                    MaybeUninit::new(unsafe { self.val(idx) }.clone())
+                } else {
+                    MaybeUninit::uninit()
+                },
+            );
         }
 
-        let slot = unsafe { self.get_slot(idx) };
-
-        match slot {
-            Slot::Value { next, .. } => *next,
-            Slot::Deleted { .. } => None,
+        Self {
+            storage,
+            head: self.head,
+            tail: self.tail,
+            deleted_tail: self.deleted_tail,
+            deleted_head: self.deleted_head,
+            reuse: self.reuse,
+            len: self.len,
+            contiguous: self.contiguous,
+            occupied: self.occupied.clone(),
+            marked: self.marked.clone(),
+            positions: self.positions.clone(),
+            _marker: PhantomData,
         }
     }
+}
 
-    pub fn previous(&self, idx: usize) -> Option<usize> {
-        if idx < self.cap() {
-            return None;
+impl<T: fmt::Debug, S: Storage<T>> fmt::Debug for VecList<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Slots<'a, T>(&'a [Link], &'a [MaybeUninit<T>]);
+        struct Slot<'a, T>(&'a Link, &'a MaybeUninit<T>);
+
+        impl<'a, T: fmt::Debug> fmt::Debug for Slots<'a, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_list()
+                    .entries(self.0.iter().zip(self.1).map(|(link, val)| Slot(link, val)))
+                    .finish()
+            }
         }
 
-        let slot = unsafe { self.get_slot(idx) };
-
-        match slot {
-            Slot::Value { prev, .. } => *prev,
-            Slot::Deleted { .. } => None,
+        impl<'a, T: fmt::Debug> fmt::Debug for Slot<'a, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let Self(link, val) = *self;
+
+                if link.has_value() {
+                    f.debug_struct("Value")
+                        .field("val", unsafe { val.assume_init_ref() })
+                        .field("next", &link.next())
+                        .field("prev", &link.prev())
+                        .finish()
+                } else {
+                    f.debug_struct("Deleted")
+                        .field("prev", &link.deleted_prev())
+                        .finish()
+                }
+            }
         }
-    }
 
-    pub fn clear(&mut self) {
-        self.list.clear();
-        self.len = 0;
-        self.head = None;
-        self.tail = None;
-        self.deleted_tail = None;
+        f.debug_struct("VecList")
+            .field("list", &Slots(self.storage.links(), self.storage.vals()))
+            .field("head", &self.head)
+            .field("tail", &self.tail)
+            .field("deleted_tail", &self.deleted_tail)
+            .field("len", &self.len)
+            .finish()
     }
+}
 
-    // SAFETY: Must in range
-    unsafe fn get_slot_mut(&mut self, idx: usize) -> &mut Slot<T> {
-        debug_assert!(idx < self.cap());
+/// A hint only, does nothing on targets without a stable prefetch instruction
+#[inline(always)]
+fn prefetch<P>(ptr: *const P) {
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        use std::arch::x86_64::_mm_prefetch;
+        use std::arch::x86_64::_MM_HINT_T0;
 
-        self.list.get_unchecked_mut(idx)
+        _mm_prefetch::<_MM_HINT_T0>(ptr as *const i8);
     }
 
-    // SAFETY: Must in range
-    unsafe fn get_slot(&self, idx: usize) -> &Slot<T> {
-        debug_assert!(idx < self.cap());
+    #[cfg(target_arch = "x86")]
+    unsafe {
+        use std::arch::x86::_mm_prefetch;
+        use std::arch::x86::_MM_HINT_T0;
 
-        self.list.get_unchecked(idx)
+        _mm_prefetch::<_MM_HINT_T0>(ptr as *const i8);
     }
+
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "x86")))]
+    let _ = ptr;
 }
 
 pub struct Iter<'a, T> {
-    list: &'a VecList<T>,
+    links: &'a [Link],
+    vals: &'a [MaybeUninit<T>],
     next: Option<usize>,
     prev: Option<usize>,
+    /* remaining, so that both ends never cross */
+    len: usize,
+}
+
+/// `Iter` which prefetches the following slot while yielding the current one,
+/// which helps when the list is fragmented and the links jump around
+pub struct Prefetch<'a, T> {
+    iter: Iter<'a, T>,
+}
+
+pub struct ChunkBy<'a, T, F> {
+    iter: Iter<'a, T>,
+    /* the first one of the next run, which ended the last one */
+    pending: Option<(&'a T, usize)>,
+    pred: F,
 }
 
 pub struct IterMut<'a, T> {
-    list: &'a mut VecList<T>,
+    links: &'a [Link],
+    /* raw, so that handing out `&'a mut T`s never reborrows the whole slice */
+    vals: *mut MaybeUninit<T>,
     next: Option<usize>,
     prev: Option<usize>,
+    /* remaining, so that both ends never cross */
+    len: usize,
+    _marker: PhantomData<&'a mut T>,
+}
+
+pub struct IntoIter<T, S: Storage<T> = VecStorage<T>> {
+    list: VecList<T, S>,
+}
+
+pub struct Drain<'a, T, S: Storage<T> = VecStorage<T>> {
+    list: &'a mut VecList<T, S>,
+}
+
+#[cfg(feature = "diagnostics")]
+pub struct FreeSlots<'a> {
+    links: &'a [Link],
+    next: Option<usize>,
 }
 
-pub struct IntoIter<T> {
-    list: VecList<T>,
+pub struct Values<'a, T> {
+    iter: Iter<'a, T>,
+}
+
+pub struct ValuesMut<'a, T> {
+    iter: IterMut<'a, T>,
 }
 
 impl<'a, T> Iterator for Iter<'a, T> {
     type Item = (&'a T, usize);
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
         let next_idx = self.next?;
 
-        let slot = unsafe { self.list.get_slot(next_idx) };
+        let link = unsafe { self.links.get_unchecked(next_idx) };
 
-        debug_assert!(slot.has_value());
+        debug_assert!(link.has_value());
 
-        match slot {
-            Slot::Value { val, next, .. } => {
-                self.next = *next;
-                Some((val, next_idx))
-            }
-            _ => unsafe { unreachable_unchecked() },
-        }
+        self.next = link.next();
+        self.len -= 1;
+        Some((
+            unsafe { self.vals.get_unchecked(next_idx).assume_init_ref() },
+            next_idx,
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
     }
 }
 
 impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
     fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
         let prev_idx = self.prev?;
 
-        let slot = unsafe { self.list.get_slot(prev_idx) };
+        let link = unsafe { self.links.get_unchecked(prev_idx) };
+
+        debug_assert!(link.has_value());
+
+        self.prev = link.prev();
+        self.len -= 1;
+        Some((
+            unsafe { self.vals.get_unchecked(prev_idx).assume_init_ref() },
+            prev_idx,
+        ))
+    }
+}
+
+impl<'a, T> Iter<'a, T> {
+    /// Opt in prefetching
+    pub fn prefetch(self) -> Prefetch<'a, T> {
+        Prefetch { iter: self }
+    }
+
+    // SAFETY: Must in range
+    unsafe fn prefetch_slot(&self, idx: usize) {
+        debug_assert!(idx < self.links.len());
+
+        prefetch(self.links.as_ptr().add(idx));
+        prefetch(self.vals.as_ptr().add(idx));
+    }
+}
+
+impl<'a, T> Iterator for Prefetch<'a, T> {
+    type Item = (&'a T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        if let (true, Some(next)) = (self.iter.len > 0, self.iter.next) {
+            unsafe { self.iter.prefetch_slot(next) };
+        }
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Prefetch<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next_back()?;
+        if let (true, Some(prev)) = (self.iter.len > 0, self.iter.prev) {
+            unsafe { self.iter.prefetch_slot(prev) };
+        }
+        Some(item)
+    }
+}
+
+impl<'a, T, F: FnMut(&T, &T) -> bool> Iterator for ChunkBy<'a, T, F> {
+    type Item = (Iter<'a, T>, usize, usize);
 
-        debug_assert!(slot.has_value());
+    fn next(&mut self) -> Option<Self::Item> {
+        let (first_val, first) = self.pending.take().or_else(|| self.iter.next())?;
 
-        match slot {
-            Slot::Value { val, prev, .. } => {
-                self.prev = *prev;
-                Some((val, prev_idx))
+        let (mut last_val, mut last, mut len) = (first_val, first, 1);
+        for (val, idx) in self.iter.by_ref() {
+            if !(self.pred)(last_val, val) {
+                self.pending = Some((val, idx));
+                break;
             }
-            _ => unsafe { unreachable_unchecked() },
+            (last_val, last) = (val, idx);
+            len += 1;
         }
+
+        let run = Iter {
+            links: self.iter.links,
+            vals: self.iter.vals,
+            next: Some(first),
+            prev: Some(last),
+            len,
+        };
+        Some((run, first, last))
     }
 }
 
@@ -548,55 +1801,144 @@ impl<'a, T> Iterator for IterMut<'a, T> {
     type Item = (&'a mut T, usize);
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
         let next_idx = self.next?;
 
-        let slot = unsafe { &mut *(self.list.get_slot_mut(next_idx) as *mut Slot<T>) };
+        let link = unsafe { self.links.get_unchecked(next_idx) };
 
-        debug_assert!(slot.has_value());
+        debug_assert!(link.has_value());
 
-        match slot {
-            Slot::Value { val, next, .. } => {
-                self.next = *next;
-                Some((val, next_idx))
-            }
-            _ => unsafe { unreachable_unchecked() },
-        }
+        self.next = link.next();
+        self.len -= 1;
+        Some((
+            unsafe { (*self.vals.add(next_idx)).assume_init_mut() },
+            next_idx,
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
     }
 }
 
 impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
     fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
         let prev_idx = self.prev?;
 
-        let slot = unsafe { &mut *(self.list.get_slot_mut(prev_idx) as *mut Slot<T>) };
+        let link = unsafe { self.links.get_unchecked(prev_idx) };
 
-        debug_assert!(slot.has_value());
+        debug_assert!(link.has_value());
 
-        match slot {
-            Slot::Value { val, prev, .. } => {
-                self.prev = *prev;
-                Some((val, prev_idx))
-            }
-            _ => unsafe { unreachable_unchecked() },
-        }
+        self.prev = link.prev();
+        self.len -= 1;
+        Some((
+            unsafe { (*self.vals.add(prev_idx)).assume_init_mut() },
+            prev_idx,
+        ))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+impl<'a, T> ExactSizeIterator for Prefetch<'a, T> {}
+
+unsafe impl<'a, T: Send> Send for IterMut<'a, T> {}
+
+unsafe impl<'a, T: Sync> Sync for IterMut<'a, T> {}
+
+#[cfg(feature = "diagnostics")]
+impl<'a> Iterator for FreeSlots<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.next?;
+        self.next = unsafe { self.links.get_unchecked(idx) }.deleted_prev();
+        Some(idx)
+    }
+}
+
+impl<'a, T> Iterator for Values<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(val, _)| val)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Values<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(val, _)| val)
+    }
+}
+
+impl<'a, T> Iterator for ValuesMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(val, _)| val)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ValuesMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(val, _)| val)
+    }
+}
+
+impl<T, S: Storage<T>> Iterator for IntoIter<T, S> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len(), Some(self.list.len()))
+    }
+}
+
+impl<T, S: Storage<T>> DoubleEndedIterator for IntoIter<T, S> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_back()
     }
 }
 
-impl<T> Iterator for IntoIter<T> {
+impl<T, S: Storage<T>> ExactSizeIterator for IntoIter<T, S> {}
+
+impl<'a, T, S: Storage<T>> Iterator for Drain<'a, T, S> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.list.pop_front()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len(), Some(self.list.len()))
+    }
 }
 
-impl<T> DoubleEndedIterator for IntoIter<T> {
+impl<'a, T, S: Storage<T>> DoubleEndedIterator for Drain<'a, T, S> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.list.pop_back()
     }
 }
 
-impl<T> ops::Index<usize> for VecList<T> {
+impl<'a, T, S: Storage<T>> ExactSizeIterator for Drain<'a, T, S> {}
+
+impl<'a, T, S: Storage<T>> Drop for Drain<'a, T, S> {
+    fn drop(&mut self) {
+        self.list.clear();
+    }
+}
+
+impl<T, S: Storage<T>> ops::Index<usize> for VecList<T, S> {
     type Output = T;
 
     fn index(&self, index: usize) -> &Self::Output {
@@ -604,23 +1946,23 @@ impl<T> ops::Index<usize> for VecList<T> {
     }
 }
 
-impl<T> ops::IndexMut<usize> for VecList<T> {
+impl<T, S: Storage<T>> ops::IndexMut<usize> for VecList<T, S> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
         self.get_mut(index).expect("invalid key!")
     }
 }
 
-impl<T> IntoIterator for VecList<T> {
+impl<T, S: Storage<T>> IntoIterator for VecList<T, S> {
     type Item = T;
 
-    type IntoIter = IntoIter<T>;
+    type IntoIter = IntoIter<T, S>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.into_iter()
+        IntoIter { list: self }
     }
 }
 
-impl<'a, T> IntoIterator for &'a VecList<T> {
+impl<'a, T, S: Storage<T>> IntoIterator for &'a VecList<T, S> {
     type Item = (&'a T, usize);
 
     type IntoIter = Iter<'a, T>;
@@ -630,7 +1972,7 @@ impl<'a, T> IntoIterator for &'a VecList<T> {
     }
 }
 
-impl<'a, T> IntoIterator for &'a mut VecList<T> {
+impl<'a, T, S: Storage<T>> IntoIterator for &'a mut VecList<T, S> {
     type Item = (&'a mut T, usize);
 
     type IntoIter = IterMut<'a, T>;
@@ -640,7 +1982,25 @@ impl<'a, T> IntoIterator for &'a mut VecList<T> {
     }
 }
 
-impl<T: fmt::Debug> fmt::Display for VecList<T> {
+/// Compares the values from front to back, indices are ignored
+impl<T: PartialEq, S: Storage<T>> PartialEq for VecList<T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.values().eq(other.values())
+    }
+}
+
+impl<T: Eq, S: Storage<T>> Eq for VecList<T, S> {}
+
+impl<T: Hash, S: Storage<T>> Hash for VecList<T, S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for val in self.values() {
+            val.hash(state);
+        }
+    }
+}
+
+impl<T: fmt::Debug, S: Storage<T>> fmt::Display for VecList<T, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.is_empty() {
             write!(f, "[]")?;
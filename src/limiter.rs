@@ -0,0 +1,68 @@
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::BoundedList;
+
+/// Sliding-window rate limiter, at most `max` events in any `window`.
+///
+/// The timestamps of the admitted events are kept oldest first,
+/// and the expired ones are popped from the front.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    events: BoundedList<Instant>,
+    window: Duration,
+}
+
+impl RateLimiter {
+    pub fn new(max: usize, window: Duration) -> Self {
+        Self {
+            events: BoundedList::new(max),
+            window,
+        }
+    }
+
+    /// Amortized O(1), admits an event at `now` if there is room in the window
+    pub fn try_acquire(&mut self, now: Instant) -> bool {
+        self.prune(now);
+        self.events.try_add(now).is_ok()
+    }
+
+    /// How many events would be admitted at `now`
+    pub fn remaining(&mut self, now: Instant) -> usize {
+        self.prune(now);
+        self.events.remaining_capacity()
+    }
+
+    /// When the next event can be admitted, `None` if it can be right away, or never with `max` 0.
+    /// Only meaningful after a `try_acquire` or `remaining` at about the same time.
+    pub fn next_free(&self) -> Option<Instant> {
+        if !self.events.is_full() {
+            return None;
+        }
+
+        self.events.front().map(|(oldest, _)| *oldest + self.window)
+    }
+
+    pub fn max(&self) -> usize {
+        self.events.capacity()
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Forgets all events
+    pub fn reset(&mut self) {
+        self.events.clear();
+    }
+
+    /* an event at `t` counts in `t..t + window`, a clock going backwards expires nothing */
+    fn prune(&mut self, now: Instant) {
+        while let Some((oldest, _)) = self.events.front() {
+            if now.saturating_duration_since(*oldest) < self.window {
+                break;
+            }
+            self.events.pop_front();
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use core::fmt;
+use std::ops::Range;
+
+use crate::VecList;
+
+/// Lines of a text buffer, one per node, the slot indices are stable line handles.
+/// The list keeps a position index, so going between line numbers and handles is O(log n).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LineList {
+    list: VecList<String>,
+}
+
+/// Lines in order with their handles, from `LineList::lines`
+pub struct Lines<'a> {
+    list: &'a VecList<String>,
+    next: Option<usize>,
+    len: usize,
+}
+
+impl LineList {
+    pub fn new() -> Self {
+        Self::from_list(VecList::new())
+    }
+
+    /// One line per `\n`, a trailing one is not an extra empty line
+    pub fn from_text(text: &str) -> Self {
+        Self::from_list(text.lines().map(String::from).collect())
+    }
+
+    fn from_list(mut list: VecList<String>) -> Self {
+        list.set_position_index(true);
+        Self { list }
+    }
+
+    /// O(1)
+    pub fn line(&self, handle: usize) -> Option<&str> {
+        self.list.get(handle).map(String::as_str)
+    }
+
+    /// O(1)
+    pub fn line_mut(&mut self, handle: usize) -> Option<&mut String> {
+        self.list.get_mut(handle)
+    }
+
+    /// O(log n)
+    pub fn handle_at(&self, pos: usize) -> Option<usize> {
+        self.list.index_at_position(pos)
+    }
+
+    /// O(log n), the line number of `handle`, `None` if it's deleted
+    pub fn position(&self, handle: usize) -> Option<usize> {
+        self.list.position_of(handle)
+    }
+
+    /// Average O(log n)
+    pub fn push_line(&mut self, line: String) -> usize {
+        self.list.push_back(line)
+    }
+
+    /// Average O(log n), panics if `handle` is deleted
+    pub fn insert_after(&mut self, handle: usize, line: String) -> usize {
+        self.list.insert_after(handle, line)
+    }
+
+    /// Average O(log n), panics if `handle` is deleted
+    pub fn insert_before(&mut self, handle: usize, line: String) -> usize {
+        self.list.insert_before(handle, line)
+    }
+
+    /// O(log n)
+    pub fn remove(&mut self, handle: usize) -> Option<String> {
+        self.list.delete(handle)
+    }
+
+    /// Moves the text from byte `at` on into a new line right after, and gives its handle.
+    /// Panics if `handle` is deleted or `at` is not on a char boundary.
+    pub fn split_line(&mut self, handle: usize, at: usize) -> usize {
+        let rest = self.list[handle].split_off(at);
+        self.list.insert_after(handle, rest)
+    }
+
+    /// Appends the next line to this one and removes it, `false` if there is no next line.
+    /// Panics if `handle` is deleted.
+    pub fn join_with_next(&mut self, handle: usize) -> bool {
+        assert!(self.list.get(handle).is_some(), "invalid key!");
+
+        let Some(next) = self.list.next(handle) else {
+            return false;
+        };
+        let next_line = self.list.delete(next).expect("invalid key!");
+        self.list[handle].push_str(&next_line);
+        true
+    }
+
+    /// The lines at positions in `range`, O(log n) to find the start
+    pub fn lines(&self, range: Range<usize>) -> Lines<'_> {
+        let end = range.end.min(self.len());
+        Lines {
+            list: &self.list,
+            next: self.list.index_at_position(range.start),
+            len: end.saturating_sub(range.start),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn as_list(&self) -> &VecList<String> {
+        &self.list
+    }
+
+    pub fn into_list(self) -> VecList<String> {
+        self.list
+    }
+}
+
+impl Default for LineList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The lines joined by `\n`
+impl fmt::Display for LineList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut sep = "";
+        for line in self.list.values() {
+            write!(f, "{}{}", sep, line)?;
+            sep = "\n";
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = (&'a str, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let idx = self.next?;
+
+        self.next = self.list.next(idx);
+        self.len -= 1;
+        Some((self.list[idx].as_str(), idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a> ExactSizeIterator for Lines<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered(lines: &LineList) {
+        assert!(lines.list.positions_synced());
+        for (pos, (_, handle)) in lines.as_list().iter().enumerate() {
+            assert_eq!(lines.handle_at(pos), Some(handle));
+            assert_eq!(lines.position(handle), Some(pos));
+        }
+        assert_eq!(lines.handle_at(lines.len()), None);
+    }
+
+    #[test]
+    fn line_numbers_follow_edits() {
+        let mut lines = LineList::from_text("a\nb\nc\nd");
+        numbered(&lines);
+
+        let b = lines.handle_at(1).unwrap();
+        let bc = lines.split_line(b, 0);
+        lines.insert_before(b, "x".into());
+        lines.push_line("e".into());
+        numbered(&lines);
+
+        assert!(lines.join_with_next(b));
+        assert_eq!(lines.position(bc), None);
+        lines.remove(lines.handle_at(0).unwrap());
+        numbered(&lines);
+
+        assert_eq!(lines.to_string(), "x\nb\nc\nd\ne");
+        let middle: Vec<&str> = lines.lines(1..3).map(|(line, _)| line).collect();
+        assert_eq!(middle, ["b", "c"]);
+    }
+
+    #[test]
+    fn new_and_default_are_indexed() {
+        let mut lines = LineList::default();
+        for i in 0..100 {
+            lines.push_line(i.to_string());
+        }
+        numbered(&lines);
+        assert_eq!(lines.line(lines.handle_at(50).unwrap()), Some("50"));
+        assert!(LineList::new().as_list().has_position_index());
+    }
+}
@@ -0,0 +1,179 @@
+/// Links of a slot, packed into two raw indices instead of an enum.
+///
+/// A slot with value has `next`/`prev` as an index or `NONE`,
+/// a deleted slot has `next == DELETED` and `prev` pointing to the previous deleted slot.
+///
+/// With the `u32-links` feature the raw indices are `u32`, so a link takes 8 bytes
+/// instead of 16 on 64-bit targets, but a list can hold at most `MAX_SLOTS` slots.
+///
+/// Opaque outside the crate, a `Storage` only moves it around.
+#[derive(Debug, Clone, Copy)]
+pub struct Link {
+    next: Raw,
+    prev: Raw,
+}
+
+#[cfg(not(feature = "u32-links"))]
+type Raw = usize;
+#[cfg(feature = "u32-links")]
+type Raw = u32;
+
+/* never real indices, `alloc` makes sure of it */
+const NONE: Raw = Raw::MAX;
+const DELETED: Raw = Raw::MAX - 1;
+
+/* `Raw` may be `usize` */
+#[allow(clippy::unnecessary_cast)]
+pub(crate) const MAX_SLOTS: usize = DELETED as usize;
+
+const _: () = assert!(std::mem::size_of::<Link>() == 2 * std::mem::size_of::<Raw>());
+
+const fn encode(idx: Option<usize>) -> Raw {
+    match idx {
+        Some(idx) => {
+            debug_assert!(idx < MAX_SLOTS);
+            idx as Raw
+        }
+        None => NONE,
+    }
+}
+
+#[allow(clippy::unnecessary_cast)]
+fn decode(raw: Raw) -> Option<usize> {
+    if raw == NONE {
+        None
+    } else {
+        Some(raw as usize)
+    }
+}
+
+impl Link {
+    pub(crate) fn value(next: Option<usize>, prev: Option<usize>) -> Self {
+        Self {
+            next: encode(next),
+            prev: encode(prev),
+        }
+    }
+
+    pub(crate) const fn deleted(prev: Option<usize>) -> Self {
+        Self {
+            next: DELETED,
+            prev: encode(prev),
+        }
+    }
+
+    pub(crate) fn is_deleted(&self) -> bool {
+        self.next == DELETED
+    }
+
+    pub(crate) fn has_value(&self) -> bool {
+        !self.is_deleted()
+    }
+
+    /// `None` if deleted
+    pub(crate) fn next(&self) -> Option<usize> {
+        if self.is_deleted() {
+            None
+        } else {
+            decode(self.next)
+        }
+    }
+
+    /// `None` if deleted
+    pub(crate) fn prev(&self) -> Option<usize> {
+        if self.is_deleted() {
+            None
+        } else {
+            decode(self.prev)
+        }
+    }
+
+    /// The previous one in the deleted chain
+    pub(crate) fn deleted_prev(&self) -> Option<usize> {
+        debug_assert!(self.is_deleted());
+
+        decode(self.prev)
+    }
+
+    pub(crate) fn set_deleted_prev(&mut self, prev: Option<usize>) {
+        debug_assert!(self.is_deleted());
+
+        self.prev = encode(prev);
+    }
+
+    pub(crate) fn set_next(&mut self, next: Option<usize>) {
+        debug_assert!(self.has_value());
+
+        self.next = encode(next);
+    }
+
+    pub(crate) fn set_prev(&mut self, prev: Option<usize>) {
+        debug_assert!(self.has_value());
+
+        self.prev = encode(prev);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sentinels_never_collide_with_indices() {
+        let link = Link::value(Some(MAX_SLOTS - 1), Some(0));
+        assert!(link.has_value());
+        assert_eq!(link.next(), Some(MAX_SLOTS - 1));
+        assert_eq!(link.prev(), Some(0));
+
+        let link = Link::value(None, None);
+        assert!(link.has_value());
+        assert_eq!((link.next(), link.prev()), (None, None));
+
+        let mut link = Link::deleted(Some(MAX_SLOTS - 1));
+        assert!(link.is_deleted());
+        assert_eq!((link.next(), link.prev()), (None, None));
+        assert_eq!(link.deleted_prev(), Some(MAX_SLOTS - 1));
+        link.set_deleted_prev(None);
+        assert_eq!(link.deleted_prev(), None);
+    }
+
+    #[test]
+    fn setters_keep_the_other_end() {
+        let mut link = Link::value(Some(1), Some(2));
+        link.set_next(None);
+        assert_eq!((link.next(), link.prev()), (None, Some(2)));
+        link.set_prev(Some(3));
+        assert_eq!((link.next(), link.prev()), (None, Some(3)));
+        assert!(link.has_value());
+    }
+
+    #[cfg(not(feature = "u32-links"))]
+    #[test]
+    fn usize_links() {
+        assert_eq!(
+            std::mem::size_of::<Link>(),
+            2 * std::mem::size_of::<usize>()
+        );
+        assert_eq!(MAX_SLOTS, usize::MAX - 1);
+    }
+
+    #[cfg(feature = "u32-links")]
+    #[test]
+    fn u32_links() {
+        assert_eq!(std::mem::size_of::<Link>(), 8);
+        assert_eq!(MAX_SLOTS, u32::MAX as usize - 1);
+
+        let mut list = crate::VecList::<u8>::new();
+        assert_eq!(
+            list.try_reserve(MAX_SLOTS + 1),
+            Err(crate::VecListError::CapacityExceeded)
+        );
+        list.extend(0..10);
+        list.delete(4);
+        assert_eq!(list.push_front(4), 4);
+        assert_eq!(
+            list.values().copied().collect::<Vec<_>>(),
+            [4, 0, 1, 2, 3, 5, 6, 7, 8, 9]
+        );
+    }
+}
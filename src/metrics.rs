@@ -0,0 +1,72 @@
+use ::metrics::counter;
+use ::metrics::describe_counter;
+use ::metrics::describe_gauge;
+use ::metrics::gauge;
+use ::metrics::Counter;
+use ::metrics::Unit;
+
+use crate::BoundedList;
+use crate::OnEvict;
+use crate::Storage;
+use crate::VecList;
+
+const LEN: &str = "vec_list_len";
+const CAPACITY: &str = "vec_list_capacity";
+const DELETED_SLOTS: &str = "vec_list_deleted_slots";
+const BOUND: &str = "vec_list_bound";
+const EVICTIONS: &str = "vec_list_evictions_total";
+
+/// Describes the metrics to the installed recorder, call it once after installing it.
+/// Each one is labeled `list` with the name it's reported under, so a recorder can watch many lists.
+pub fn describe() {
+    describe_gauge!(LEN, Unit::Count, "elements in the list");
+    describe_gauge!(CAPACITY, Unit::Count, "slots the list has room for");
+    describe_gauge!(
+        DELETED_SLOTS,
+        Unit::Count,
+        "deleted slots waiting for reuse"
+    );
+    describe_gauge!(BOUND, Unit::Count, "cap of a bounded list");
+    describe_counter!(
+        EVICTIONS,
+        Unit::Count,
+        "elements a bounded list dropped on its own"
+    );
+}
+
+/// An `OnEvict` which counts the evicted elements under `name` and drops them
+#[derive(Debug, Clone)]
+pub struct CountEvictions {
+    counter: Counter,
+}
+
+impl CountEvictions {
+    pub fn new(name: &str) -> Self {
+        Self {
+            counter: counter!(EVICTIONS, "list" => name.to_owned()),
+        }
+    }
+}
+
+impl<T> OnEvict<T> for CountEvictions {
+    fn on_evict(&mut self, _: T) {
+        self.counter.increment(1);
+    }
+}
+
+impl<T, S: Storage<T>> VecList<T, S> {
+    /// O(1), sets the len, capacity and deleted slots gauges of `name`
+    pub fn report_metrics(&self, name: &str) {
+        gauge!(LEN, "list" => name.to_owned()).set(self.len() as f64);
+        gauge!(CAPACITY, "list" => name.to_owned()).set(self.vec_cap() as f64);
+        gauge!(DELETED_SLOTS, "list" => name.to_owned()).set((self.cap() - self.len()) as f64);
+    }
+}
+
+impl<T, E: OnEvict<T>, S: Storage<T>> BoundedList<T, E, S> {
+    /// O(1), like `VecList::report_metrics`, and the cap
+    pub fn report_metrics(&self, name: &str) {
+        self.as_list().report_metrics(name);
+        gauge!(BOUND, "list" => name.to_owned()).set(self.capacity() as f64);
+    }
+}
@@ -0,0 +1,354 @@
+use crate::link;
+use crate::Link;
+
+/// Elements which can be in up to `K` orderings at once, e.g. by insertion and by priority,
+/// each slot has one pair of links per ordering.
+///
+/// `insert` stores an element in none of them, then it's linked into each ordering on its own.
+#[derive(Debug, Clone)]
+pub struct MultiList<T, const K: usize> {
+    /* `None` if the slot is free */
+    vals: Vec<Option<T>>,
+    /* a deleted link means not in that ordering */
+    links: Vec<[Link; K]>,
+    heads: [Option<usize>; K],
+    tails: [Option<usize>; K],
+    lens: [usize; K],
+    free: Vec<usize>,
+    len: usize,
+}
+
+/// One ordering of a `MultiList`, from `MultiList::iter`
+pub struct MultiIter<'a, T, const K: usize> {
+    list: &'a MultiList<T, K>,
+    k: usize,
+    next: Option<usize>,
+    prev: Option<usize>,
+    /* remaining, so that both ends never cross */
+    len: usize,
+}
+
+impl<T, const K: usize> MultiList<T, K> {
+    pub fn new() -> Self {
+        Self {
+            vals: Vec::new(),
+            links: Vec::new(),
+            heads: [None; K],
+            tails: [None; K],
+            lens: [0; K],
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Average O(1), in no ordering yet
+    pub fn insert(&mut self, val: T) -> usize {
+        self.len += 1;
+
+        match self.free.pop() {
+            Some(idx) => {
+                self.vals[idx] = Some(val);
+                idx
+            }
+            None => {
+                assert!(self.vals.len() < link::MAX_SLOTS, "too many slots!");
+
+                self.vals.push(Some(val));
+                self.links.push([Link::deleted(None); K]);
+                self.vals.len() - 1
+            }
+        }
+    }
+
+    /// O(K), takes it out of every ordering
+    pub fn remove(&mut self, idx: usize) -> Option<T> {
+        self.get(idx)?;
+        for k in 0..K {
+            self.unlink(k, idx);
+        }
+        let val = self.vals[idx].take()?;
+
+        self.free.push(idx);
+        self.len -= 1;
+        Some(val)
+    }
+
+    /// O(1), panics if `idx` is removed or already in ordering `k`
+    pub fn push_back(&mut self, k: usize, idx: usize) {
+        self.assert_out_of(k, idx);
+
+        self.link_between(k, self.tails[k], None, idx);
+    }
+
+    /// O(1), panics if `idx` is removed or already in ordering `k`
+    pub fn push_front(&mut self, k: usize, idx: usize) {
+        self.assert_out_of(k, idx);
+
+        self.link_between(k, None, self.heads[k], idx);
+    }
+
+    /// O(1), links `idx` right after `at` in ordering `k`,
+    /// panics if `idx` is removed or already in it, or if `at` is not in it
+    pub fn insert_after(&mut self, k: usize, at: usize, idx: usize) {
+        self.assert_out_of(k, idx);
+        assert!(self.contains(k, at), "not in this ordering!");
+
+        self.link_between(k, Some(at), self.links[at][k].next(), idx);
+    }
+
+    /// O(1), links `idx` right before `at` in ordering `k`,
+    /// panics if `idx` is removed or already in it, or if `at` is not in it
+    pub fn insert_before(&mut self, k: usize, at: usize, idx: usize) {
+        self.assert_out_of(k, idx);
+        assert!(self.contains(k, at), "not in this ordering!");
+
+        self.link_between(k, self.links[at][k].prev(), Some(at), idx);
+    }
+
+    /// O(1), takes `idx` out of ordering `k` only, `false` if it was not in it
+    pub fn unlink(&mut self, k: usize, idx: usize) -> bool {
+        if !self.contains(k, idx) {
+            return false;
+        }
+
+        let link = self.links[idx][k];
+        let (prev, next) = (link.prev(), link.next());
+        match prev {
+            Some(prev) => self.links[prev][k].set_next(next),
+            None => self.heads[k] = next,
+        }
+        match next {
+            Some(next) => self.links[next][k].set_prev(prev),
+            None => self.tails[k] = prev,
+        }
+
+        self.links[idx][k] = Link::deleted(None);
+        self.lens[k] -= 1;
+        true
+    }
+
+    /// O(1), whether `idx` is in ordering `k`
+    pub fn contains(&self, k: usize, idx: usize) -> bool {
+        assert!(k < K, "no such ordering!");
+
+        self.get(idx).is_some() && self.links[idx][k].has_value()
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        self.vals.get(idx)?.as_ref()
+    }
+
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        self.vals.get_mut(idx)?.as_mut()
+    }
+
+    /// O(1), in ordering `k`
+    pub fn front(&self, k: usize) -> Option<(&T, usize)> {
+        let head = self.heads[k]?;
+        Some((self.get(head)?, head))
+    }
+
+    /// O(1), in ordering `k`
+    pub fn back(&self, k: usize) -> Option<(&T, usize)> {
+        let tail = self.tails[k]?;
+        Some((self.get(tail)?, tail))
+    }
+
+    /// O(1), in ordering `k`
+    pub fn next(&self, k: usize, idx: usize) -> Option<usize> {
+        self.links.get(idx)?[k].next()
+    }
+
+    /// O(1), in ordering `k`
+    pub fn previous(&self, k: usize, idx: usize) -> Option<usize> {
+        self.links.get(idx)?[k].prev()
+    }
+
+    pub fn iter(&self, k: usize) -> MultiIter<'_, T, K> {
+        MultiIter {
+            list: self,
+            k,
+            next: self.heads[k],
+            prev: self.tails[k],
+            len: self.lens[k],
+        }
+    }
+
+    /// All the stored elements, in or out of orderings
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The elements in ordering `k`
+    pub fn len_of(&self, k: usize) -> usize {
+        self.lens[k]
+    }
+
+    /* `prev` and `next` are neighbors in ordering `k`, `None` for its ends */
+    fn link_between(&mut self, k: usize, prev: Option<usize>, next: Option<usize>, idx: usize) {
+        self.links[idx][k] = Link::value(next, prev);
+        match prev {
+            Some(prev) => self.links[prev][k].set_next(Some(idx)),
+            None => self.heads[k] = Some(idx),
+        }
+        match next {
+            Some(next) => self.links[next][k].set_prev(Some(idx)),
+            None => self.tails[k] = Some(idx),
+        }
+
+        self.lens[k] += 1;
+    }
+
+    fn assert_out_of(&self, k: usize, idx: usize) {
+        assert!(self.get(idx).is_some(), "invalid key!");
+        assert!(!self.contains(k, idx), "already in this ordering!");
+    }
+}
+
+impl<T, const K: usize> Default for MultiList<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T, const K: usize> Iterator for MultiIter<'a, T, K> {
+    type Item = (&'a T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let idx = self.next?;
+
+        self.next = self.list.next(self.k, idx);
+        self.len -= 1;
+        Some((self.list.get(idx)?, idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T, const K: usize> DoubleEndedIterator for MultiIter<'a, T, K> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let idx = self.prev?;
+
+        self.prev = self.list.previous(self.k, idx);
+        self.len -= 1;
+        Some((self.list.get(idx)?, idx))
+    }
+}
+
+impl<'a, T, const K: usize> ExactSizeIterator for MultiIter<'a, T, K> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BY_ARRIVAL: usize = 0;
+    const BY_PRIORITY: usize = 1;
+
+    /* (name, priority) in arrival order, and by priority, the highest first */
+    type Jobs = MultiList<(char, u32), 2>;
+
+    fn names(jobs: &Jobs, k: usize) -> String {
+        let forward: String = jobs.iter(k).map(|(job, _)| job.0).collect();
+        let backward: String = jobs.iter(k).rev().map(|(job, _)| job.0).collect();
+        assert_eq!(forward.chars().rev().collect::<String>(), backward);
+        assert_eq!(forward.len(), jobs.len_of(k));
+        forward
+    }
+
+    fn add(jobs: &mut Jobs, name: char, priority: u32) -> usize {
+        let idx = jobs.insert((name, priority));
+        jobs.push_back(BY_ARRIVAL, idx);
+
+        /* before the first one with a lower priority, so equal ones stay in arrival order */
+        let lower = jobs
+            .iter(BY_PRIORITY)
+            .find(|(job, _)| job.1 < priority)
+            .map(|(_, at)| at);
+        match lower {
+            Some(at) => jobs.insert_before(BY_PRIORITY, at, idx),
+            None => jobs.push_back(BY_PRIORITY, idx),
+        }
+        idx
+    }
+
+    #[test]
+    fn keeps_a_priority_ordering() {
+        let mut jobs = Jobs::new();
+        for (name, priority) in [('a', 1), ('b', 5), ('c', 3), ('d', 5), ('e', 0)] {
+            add(&mut jobs, name, priority);
+        }
+        assert_eq!(names(&jobs, BY_ARRIVAL), "abcde");
+        assert_eq!(names(&jobs, BY_PRIORITY), "bdcae");
+
+        let (_, c) = jobs.iter(BY_ARRIVAL).nth(2).unwrap();
+        let x = jobs.insert(('x', 3));
+        jobs.insert_after(BY_PRIORITY, c, x);
+        jobs.insert_after(BY_ARRIVAL, c, x);
+        assert_eq!(names(&jobs, BY_ARRIVAL), "abcxde");
+        assert_eq!(names(&jobs, BY_PRIORITY), "bdcxae");
+
+        /* at both ends of an ordering */
+        let (_, e) = jobs.back(BY_PRIORITY).unwrap();
+        let (_, b) = jobs.front(BY_PRIORITY).unwrap();
+        let y = jobs.insert(('y', 9));
+        let z = jobs.insert(('z', 0));
+        jobs.insert_before(BY_PRIORITY, b, y);
+        jobs.insert_after(BY_PRIORITY, e, z);
+        assert_eq!(names(&jobs, BY_PRIORITY), "ybdcxaez");
+        assert_eq!(names(&jobs, BY_ARRIVAL), "abcxde");
+    }
+
+    #[test]
+    fn remove_unlinks_from_every_ordering() {
+        let mut jobs = Jobs::new();
+        let idxs: Vec<usize> = [('a', 1), ('b', 5), ('c', 3)]
+            .into_iter()
+            .map(|(name, priority)| add(&mut jobs, name, priority))
+            .collect();
+
+        for (name, idx) in [('b', idxs[1]), ('a', idxs[0])] {
+            assert_eq!(jobs.remove(idx).map(|job| job.0), Some(name));
+            assert!(!jobs.contains(BY_ARRIVAL, idx) && !jobs.contains(BY_PRIORITY, idx));
+        }
+        assert_eq!(names(&jobs, BY_ARRIVAL), "c");
+        assert_eq!(names(&jobs, BY_PRIORITY), "c");
+        assert_eq!(jobs.remove(idxs[0]), None);
+        assert_eq!(jobs.len(), 1);
+
+        /* a reused slot comes back in no ordering */
+        let d = jobs.insert(('d', 7));
+        assert!(idxs.contains(&d));
+        assert!(!jobs.contains(BY_ARRIVAL, d) && !jobs.contains(BY_PRIORITY, d));
+        assert_eq!(names(&jobs, BY_PRIORITY), "c");
+
+        jobs.remove(idxs[2]);
+        assert_eq!(names(&jobs, BY_ARRIVAL), "");
+        assert_eq!(names(&jobs, BY_PRIORITY), "");
+        assert_eq!(
+            (jobs.front(BY_ARRIVAL), jobs.back(BY_PRIORITY)),
+            (None, None)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "not in this ordering!")]
+    fn insert_after_one_out_of_the_ordering_panics() {
+        let mut jobs = Jobs::new();
+        let a = jobs.insert(('a', 0));
+        let b = jobs.insert(('b', 0));
+        jobs.push_back(BY_ARRIVAL, a);
+        jobs.insert_after(BY_PRIORITY, a, b);
+    }
+}
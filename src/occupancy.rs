@@ -0,0 +1,145 @@
+use std::mem::MaybeUninit;
+
+use crate::Link;
+use crate::Storage;
+use crate::VecList;
+
+const BITS: usize = u64::BITS as usize;
+
+/// The values in slot order with their indices, from `VecList::iter_unordered`
+pub struct Unordered<'a, T> {
+    links: &'a [Link],
+    vals: &'a [MaybeUninit<T>],
+    occupied: Option<&'a [u64]>,
+    /* the next slot, or the next word with a bitmap */
+    next: usize,
+    /* bits not yet yielded of the word before `next` */
+    word: u64,
+    len: usize,
+}
+
+impl<T, S: Storage<T>> VecList<T, S> {
+    /// O(cap) to turn it on, then the list keeps a bit per slot telling if it has a value,
+    /// so `iter_unordered` skips 64 deleted slots at a time, it's off by default
+    pub fn set_occupancy_tracking(&mut self, on: bool) {
+        self.occupied = on.then(|| {
+            let mut bits = Vec::new();
+            for (idx, link) in self.storage.links().iter().enumerate() {
+                set(&mut bits, idx, link.has_value());
+            }
+            bits
+        });
+    }
+
+    pub fn is_tracking_occupancy(&self) -> bool {
+        self.occupied.is_some()
+    }
+
+    /// O(cap), the values in slot order instead of list order, which reads the slots
+    /// front to back, with occupancy tracking runs of deleted slots are skipped a word at a time
+    pub fn iter_unordered(&self) -> Unordered<'_, T> {
+        Unordered {
+            links: self.storage.links(),
+            vals: self.storage.vals(),
+            occupied: self.occupied.as_deref(),
+            next: 0,
+            word: 0,
+            len: self.len,
+        }
+    }
+
+    /// Keeps the bitmap in sync, if there is one
+    pub(crate) fn mark_occupied(&mut self, idx: usize, live: bool) {
+        if let Some(bits) = &mut self.occupied {
+            set(bits, idx, live);
+        }
+    }
+}
+
+/// Sets or clears bit `idx`, growing `bits` only to set it
+pub(crate) fn set(bits: &mut Vec<u64>, idx: usize, live: bool) {
+    let (word, bit) = (idx / BITS, idx % BITS);
+    if word >= bits.len() {
+        if !live {
+            return;
+        }
+        bits.resize(word + 1, 0);
+    }
+
+    if live {
+        bits[word] |= 1 << bit;
+    } else {
+        bits[word] &= !(1 << bit);
+    }
+}
+
+pub(crate) fn get(bits: &[u64], idx: usize) -> bool {
+    bits.get(idx / BITS)
+        .is_some_and(|word| word & (1 << (idx % BITS)) != 0)
+}
+
+/// Clears the bits set in `mask`, a word at a time
+pub(crate) fn clear_all(bits: &mut [u64], mask: &[u64]) {
+    for (word, mask) in bits.iter_mut().zip(mask) {
+        *word &= !mask;
+    }
+}
+
+/// The set bits in order
+pub(crate) fn ones(bits: &[u64]) -> impl Iterator<Item = usize> + '_ {
+    bits.iter().enumerate().flat_map(|(word_idx, &word)| {
+        let mut word = word;
+        std::iter::from_fn(move || {
+            if word == 0 {
+                return None;
+            }
+            let bit = word.trailing_zeros() as usize;
+            word &= word - 1;
+            Some(word_idx * BITS + bit)
+        })
+    })
+}
+
+impl<'a, T> Iterator for Unordered<'a, T> {
+    type Item = (&'a T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let idx = match self.occupied {
+            Some(occupied) => {
+                while self.word == 0 {
+                    self.word = *occupied.get(self.next)?;
+                    self.next += 1;
+                }
+
+                let bit = self.word.trailing_zeros() as usize;
+                self.word &= self.word - 1;
+                (self.next - 1) * BITS + bit
+            }
+            None => loop {
+                let idx = self.next;
+                self.next += 1;
+                if self.links.get(idx)?.has_value() {
+                    break idx;
+                }
+            },
+        };
+
+        debug_assert!(self.links[idx].has_value());
+
+        self.len -= 1;
+        Some((
+            unsafe { self.vals.get_unchecked(idx).assume_init_ref() },
+            idx,
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Unordered<'a, T> {}
@@ -0,0 +1,414 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::mem;
+
+use crate::Drain;
+use crate::Values;
+use crate::VecList;
+
+/// A map which keeps its entries in insertion order, `move_to_front` and `move_to_back`
+/// reorder them, so recency or pinning can be built on top of it
+#[derive(Debug, Clone)]
+pub struct OrderedMap<K, V> {
+    /* in order */
+    entries: VecList<(K, V)>,
+    map: HashMap<K, usize>,
+}
+
+/// A key's place in an `OrderedMap`, from `OrderedMap::entry`
+pub enum OrderedMapEntry<'a, K, V> {
+    Occupied(OccupiedOrderedMapEntry<'a, K, V>),
+    Vacant(VacantOrderedMapEntry<'a, K, V>),
+}
+
+/// A key which is in the map
+pub struct OccupiedOrderedMapEntry<'a, K, V> {
+    map: &'a mut OrderedMap<K, V>,
+    idx: usize,
+}
+
+/// A key which is not in the map
+pub struct VacantOrderedMapEntry<'a, K, V> {
+    map: &'a mut OrderedMap<K, V>,
+    key: K,
+}
+
+impl<K: Hash + Eq + Clone, V> OrderedMap<K, V> {
+    pub fn new() -> Self {
+        Self {
+            entries: VecList::new(),
+            map: HashMap::new(),
+        }
+    }
+
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            entries: VecList::with_capacity(cap),
+            map: HashMap::with_capacity(cap),
+        }
+    }
+
+    /// Average O(1), a new key goes to the back,
+    /// an existing one keeps its place and gives back the old value
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        match self.map.get(&key) {
+            Some(&idx) => Some(mem::replace(&mut self.entries[idx].1, val)),
+            None => {
+                self.push_back(key, val);
+                None
+            }
+        }
+    }
+
+    /// Average O(1)
+    pub fn entry(&mut self, key: K) -> OrderedMapEntry<'_, K, V> {
+        match self.map.get(&key) {
+            Some(&idx) => OrderedMapEntry::Occupied(OccupiedOrderedMapEntry { map: self, idx }),
+            None => OrderedMapEntry::Vacant(VacantOrderedMapEntry { map: self, key }),
+        }
+    }
+
+    /// Average O(1)
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = *self.map.get(key)?;
+        self.entries.get(idx).map(|(_, val)| val)
+    }
+
+    /// Average O(1)
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = *self.map.get(key)?;
+        self.entries.get_mut(idx).map(|(_, val)| val)
+    }
+
+    /// Average O(1)
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Average O(1), the others keep their order
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.map.remove(key)?;
+        self.entries.delete(idx).map(|(_, val)| val)
+    }
+
+    /// Average O(1), `false` if `key` is not in the map
+    pub fn move_to_front<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.map.get(key) {
+            Some(&idx) => {
+                self.relink(idx, true);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Average O(1), `false` if `key` is not in the map
+    pub fn move_to_back<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.map.get(key) {
+            Some(&idx) => {
+                self.relink(idx, false);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// O(1)
+    pub fn front(&self) -> Option<(&K, &V)> {
+        self.entries.front().map(|((key, val), _)| (key, val))
+    }
+
+    /// O(1)
+    pub fn back(&self) -> Option<(&K, &V)> {
+        self.entries.back().map(|((key, val), _)| (key, val))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.map.clear();
+    }
+
+    /// O(n), keeps the order of the ones `f` keeps
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) {
+        let map = &mut self.map;
+        self.entries.retain_indexed(|_, (key, val)| {
+            let keep = f(key, val);
+            if !keep {
+                map.remove(key);
+            }
+            keep
+        });
+    }
+
+    /// Removes all entries in order, the rest are dropped if the `Drain` is dropped early
+    pub fn drain(&mut self) -> Drain<'_, (K, V)> {
+        self.map.clear();
+        self.entries.drain()
+    }
+
+    /// In order
+    pub fn iter(&self) -> Values<'_, (K, V)> {
+        self.entries.values()
+    }
+
+    fn push_back(&mut self, key: K, val: V) -> usize {
+        let idx = self.entries.push_back((key.clone(), val));
+        self.map.insert(key, idx);
+        idx
+    }
+
+    /// Takes `idx` out and links it back in at one end, in the same slot, so the map stays valid
+    fn relink(&mut self, idx: usize, to_front: bool) {
+        let entry = self.entries.delete(idx).expect("entry is not in the map!");
+
+        /* the slot just freed is the one taken first */
+        let new_idx = match to_front {
+            true => self.entries.push_front(entry),
+            false => self.entries.push_back(entry),
+        };
+        debug_assert_eq!(new_idx, idx);
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Default for OrderedMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, K: Hash + Eq + Clone, V> OrderedMapEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        match self {
+            Self::Occupied(entry) => entry.key(),
+            Self::Vacant(entry) => entry.key(),
+        }
+    }
+
+    pub fn or_insert(self, val: V) -> &'a mut V {
+        self.or_insert_with(|| val)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(self, f: F) -> &'a mut V {
+        match self {
+            Self::Occupied(entry) => entry.into_mut(),
+            Self::Vacant(entry) => entry.insert(f()),
+        }
+    }
+
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Self::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K: Hash + Eq + Clone, V> OccupiedOrderedMapEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.map.entries[self.idx].0
+    }
+
+    pub fn get(&self) -> &V {
+        &self.map.entries[self.idx].1
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.entries[self.idx].1
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.entries[self.idx].1
+    }
+
+    /// Gives back the old value, the entry keeps its place
+    pub fn insert(&mut self, val: V) -> V {
+        mem::replace(self.get_mut(), val)
+    }
+
+    /// O(1)
+    pub fn move_to_front(&mut self) {
+        self.map.relink(self.idx, true);
+    }
+
+    /// O(1)
+    pub fn move_to_back(&mut self) {
+        self.map.relink(self.idx, false);
+    }
+
+    /// Average O(1)
+    pub fn remove(self) -> V {
+        let (key, val) = self
+            .map
+            .entries
+            .delete(self.idx)
+            .expect("entry is not in the map!");
+        self.map.map.remove(&key);
+        val
+    }
+}
+
+impl<'a, K: Hash + Eq + Clone, V> VacantOrderedMapEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Average O(1), at the back
+    pub fn insert(self, val: V) -> &'a mut V {
+        let idx = self.map.push_back(self.key, val);
+        &mut self.map.entries[idx].1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(map: &OrderedMap<char, u32>) -> String {
+        map.iter().map(|(key, _)| key).collect()
+    }
+
+    fn map(keys: &str) -> OrderedMap<char, u32> {
+        let mut map = OrderedMap::new();
+        for (i, key) in keys.chars().enumerate() {
+            map.insert(key, i as u32);
+        }
+        map
+    }
+
+    #[test]
+    fn keeps_insertion_order() {
+        let mut map = map("cab");
+        assert_eq!(map.insert('a', 10), Some(1));
+        assert_eq!(keys(&map), "cab");
+        assert_eq!(map.get(&'a'), Some(&10));
+
+        assert_eq!(map.remove(&'a'), Some(10));
+        assert_eq!(map.remove(&'a'), None);
+        map.insert('a', 3);
+        assert_eq!(keys(&map), "cba");
+        assert_eq!(map.front(), Some((&'c', &0)));
+        assert_eq!(map.back(), Some((&'a', &3)));
+        assert_eq!(map.len(), 3);
+    }
+
+    #[test]
+    fn moves_to_either_end() {
+        let mut map = map("abcd");
+        assert!(map.move_to_back(&'b'));
+        assert_eq!(keys(&map), "acdb");
+        assert!(map.move_to_front(&'d'));
+        assert_eq!(keys(&map), "dacb");
+        assert!(map.move_to_front(&'d') && map.move_to_back(&'b'));
+        assert_eq!(keys(&map), "dacb");
+        assert!(!map.move_to_back(&'x'));
+
+        /* the moved ones are still found */
+        for (key, val) in [('a', 0), ('b', 1), ('c', 2), ('d', 3)] {
+            assert_eq!(map.get(&key), Some(&val));
+        }
+    }
+
+    #[test]
+    fn entry_upserts_and_moves() {
+        let mut map = map("ab");
+        for key in "cac".chars() {
+            *map.entry(key).or_insert(0) += 10;
+        }
+        assert_eq!(keys(&map), "abc");
+        assert_eq!((map.get(&'a'), map.get(&'c')), (Some(&10), Some(&20)));
+
+        map.entry('b').and_modify(|val| *val += 1).or_insert(0);
+        assert_eq!(map.get(&'b'), Some(&2));
+
+        match map.entry('a') {
+            OrderedMapEntry::Occupied(mut entry) => {
+                entry.move_to_back();
+                assert_eq!(entry.insert(5), 10);
+                entry.move_to_front();
+                assert_eq!(entry.get(), &5);
+            }
+            OrderedMapEntry::Vacant(_) => unreachable!(),
+        }
+        assert_eq!(keys(&map), "abc");
+
+        match map.entry('b') {
+            OrderedMapEntry::Occupied(entry) => assert_eq!(entry.remove(), 2),
+            OrderedMapEntry::Vacant(_) => unreachable!(),
+        }
+        match map.entry('z') {
+            OrderedMapEntry::Vacant(entry) => assert_eq!(entry.key(), &'z'),
+            OrderedMapEntry::Occupied(_) => unreachable!(),
+        }
+        assert_eq!(keys(&map), "ac");
+        assert!(!map.contains_key(&'b') && !map.contains_key(&'z'));
+    }
+
+    #[test]
+    fn retain_keeps_the_order() {
+        let mut map = map("abcdef");
+        map.move_to_front(&'e');
+        map.retain(|key, val| {
+            *val += 10;
+            !"bcd".contains(*key)
+        });
+        assert_eq!(keys(&map), "eaf");
+        assert_eq!(map.get(&'e'), Some(&14));
+        assert!(!map.contains_key(&'c'));
+
+        /* the removed keys can come back, at the back */
+        map.insert('c', 0);
+        assert_eq!(keys(&map), "eafc");
+    }
+
+    #[test]
+    fn drain_in_order() {
+        let mut map = map("abc");
+        map.move_to_back(&'a');
+        let drained: Vec<(char, u32)> = map.drain().collect();
+        assert_eq!(drained, [('b', 1), ('c', 2), ('a', 0)]);
+        assert!(map.is_empty() && !map.contains_key(&'a'));
+
+        let mut map = self::map("abc");
+        assert_eq!(map.drain().next(), Some(('a', 0)));
+        assert!(map.is_empty() && map.get(&'b').is_none());
+        map.insert('b', 5);
+        assert_eq!(keys(&map), "b");
+    }
+}
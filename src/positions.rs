@@ -0,0 +1,617 @@
+use std::cmp::Ordering;
+
+use crate::Storage;
+use crate::VecList;
+use crate::VecStorage;
+
+/// Maps positions from the front to slot indices and back in O(1), from `VecList::positions`.
+/// It borrows the list, so it can't go stale, build a new one after editing.
+pub struct Positions<'a, T, S: Storage<T> = VecStorage<T>> {
+    list: &'a VecList<T, S>,
+    /* slot index at each position */
+    indices: Vec<usize>,
+    /* position of each slot, `None` if deleted */
+    positions: Vec<Option<usize>>,
+}
+
+/* no node */
+const NIL: usize = usize::MAX;
+
+/// An order statistics treap over the list order, with a node per slot,
+/// from `VecList::set_position_index`
+#[derive(Debug, Clone)]
+pub(crate) struct PositionIndex {
+    nodes: Vec<Node>,
+    root: usize,
+    /* xorshift64* state for the priorities, never 0 */
+    seed: u64,
+    /* links were edited behind its back */
+    stale: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    left: usize,
+    right: usize,
+    parent: usize,
+    /* nodes in the subtree, 0 for a deleted slot */
+    size: usize,
+    priority: u64,
+}
+
+const EMPTY: Node = Node {
+    left: NIL,
+    right: NIL,
+    parent: NIL,
+    size: 0,
+    priority: 0,
+};
+
+impl<T, S: Storage<T>> VecList<T, S> {
+    /// O(n) to turn on, then the list keeps a balanced tree of its slots in list order,
+    /// so `index_at_position` and `position_of` are O(log n), it's off by default.
+    ///
+    /// `push_*`, `insert_*` and `delete` keep it up to date in O(log n), any other edit leaves it
+    /// stale, then lookups walk the list till `insert_at_position` or `split_at_position`
+    /// rebuild it in O(n).
+    pub fn set_position_index(&mut self, on: bool) {
+        self.positions = on.then(|| {
+            let seed = self.positions.as_ref().map_or(1, |index| index.seed);
+            Box::new(PositionIndex::build(
+                self.iter().map(|(_, idx)| idx),
+                self.cap(),
+                seed,
+            ))
+        });
+    }
+
+    pub fn has_position_index(&self) -> bool {
+        self.positions.is_some()
+    }
+
+    /// O(log n) with an up to date position index, O(min(pos, len - pos)) otherwise,
+    /// walking from the nearer end
+    pub fn index_at_position(&self, pos: usize) -> Option<usize> {
+        if pos >= self.len {
+            return None;
+        }
+        if let Some(index) = self.synced_positions() {
+            return index.select(pos);
+        }
+
+        if pos < self.len / 2 {
+            self.iter().nth(pos).map(|(_, idx)| idx)
+        } else {
+            self.iter()
+                .rev()
+                .nth(self.len - 1 - pos)
+                .map(|(_, idx)| idx)
+        }
+    }
+
+    /// O(log n) with an up to date position index, O(pos) otherwise, `None` if `idx` is deleted
+    pub fn position_of(&self, idx: usize) -> Option<usize> {
+        self.get(idx)?;
+        if let Some(index) = self.synced_positions() {
+            return Some(index.rank(idx));
+        }
+
+        let mut pos = 0;
+        let mut cur = idx;
+        while let Some(prev) = self.previous(cur) {
+            (cur, pos) = (prev, pos + 1);
+        }
+        Some(pos)
+    }
+
+    /// Like `index_at_position`, then `insert_before`, or `push_back` if `pos` is `len`,
+    /// a stale position index is rebuilt first.
+    /// Panics if `pos` is greater than `len`.
+    pub fn insert_at_position(&mut self, pos: usize, val: T) -> usize {
+        assert!(pos <= self.len, "out of bounds!");
+        self.sync_positions();
+
+        match self.index_at_position(pos) {
+            Some(idx) => self.insert_before(idx, val),
+            None => self.push_back(val),
+        }
+    }
+
+    /// Like `index_at_position`, then moves the elements from `pos` on into a new list,
+    /// which is empty if `pos` is not less than `len`, a stale position index is rebuilt first
+    pub fn split_at_position(&mut self, pos: usize) -> Self {
+        self.sync_positions();
+
+        match (self.index_at_position(pos), self.tail) {
+            (Some(from), Some(to)) => self.cut_range(from, to),
+            _ => Self::default(),
+        }
+    }
+
+    /// O(n) to build, then positional lookups are O(1)
+    pub fn positions(&self) -> Positions<'_, T, S> {
+        let mut indices = Vec::with_capacity(self.len);
+        let mut positions = vec![None; self.cap()];
+        for (pos, (_, idx)) in self.iter().enumerate() {
+            indices.push(idx);
+            positions[idx] = Some(pos);
+        }
+
+        Positions {
+            list: self,
+            indices,
+            positions,
+        }
+    }
+
+    /// Whether the position index is on and up to date, checked before an edit it keeps up
+    pub(crate) fn positions_synced(&self) -> bool {
+        self.synced_positions().is_some()
+    }
+
+    /// `idx` was just linked in between `prev` and `next`, the index was synced before
+    pub(crate) fn positions_inserted(
+        &mut self,
+        idx: usize,
+        prev: Option<usize>,
+        next: Option<usize>,
+    ) {
+        if let Some(index) = &mut self.positions {
+            index.insert(idx, prev, next);
+            index.stale = false;
+        }
+    }
+
+    /// `idx` was just unlinked, the index was synced before
+    pub(crate) fn positions_removed(&mut self, idx: usize) {
+        if let Some(index) = &mut self.positions {
+            index.remove(idx);
+            index.stale = false;
+        }
+    }
+
+    /// Links were edited in a way the index doesn't follow
+    pub(crate) fn positions_changed(&mut self) {
+        if let Some(index) = &mut self.positions {
+            index.stale = true;
+        }
+    }
+
+    fn synced_positions(&self) -> Option<&PositionIndex> {
+        self.positions
+            .as_deref()
+            .filter(|index| !index.stale && index.len() == self.len)
+    }
+
+    /// Rebuilds a stale position index
+    fn sync_positions(&mut self) {
+        if self.positions.is_some() && !self.positions_synced() {
+            self.set_position_index(true);
+        }
+    }
+}
+
+impl PositionIndex {
+    /// O(n), `order` are the slots in list order, `cap` is the slot count
+    fn build<I: Iterator<Item = usize>>(order: I, cap: usize, seed: u64) -> Self {
+        let mut index = Self {
+            nodes: vec![EMPTY; cap],
+            root: NIL,
+            seed,
+            stale: false,
+        };
+
+        /* the right spine, each one is the right child of the one before */
+        let mut spine: Vec<usize> = Vec::new();
+        for idx in order {
+            let priority = index.next_priority();
+            index.nodes[idx] = Node {
+                size: 1,
+                priority,
+                ..EMPTY
+            };
+
+            let mut left = NIL;
+            while let Some(&top) = spine.last() {
+                if index.nodes[top].priority >= priority {
+                    break;
+                }
+                /* its right subtree is done, so is its size */
+                index.update(top);
+                spine.pop();
+                left = top;
+            }
+
+            index.nodes[idx].left = left;
+            if left != NIL {
+                index.nodes[left].parent = idx;
+            }
+            if let Some(&top) = spine.last() {
+                index.nodes[top].right = idx;
+                index.nodes[idx].parent = top;
+            }
+            spine.push(idx);
+        }
+        while let Some(top) = spine.pop() {
+            index.update(top);
+            index.root = top;
+        }
+
+        index
+    }
+
+    fn len(&self) -> usize {
+        self.size(self.root)
+    }
+
+    /// O(log n), the slot at `pos`
+    fn select(&self, mut pos: usize) -> Option<usize> {
+        if pos >= self.len() {
+            return None;
+        }
+
+        let mut cur = self.root;
+        loop {
+            let Node { left, right, .. } = self.nodes[cur];
+            let left_size = self.size(left);
+            match pos.cmp(&left_size) {
+                Ordering::Less => cur = left,
+                Ordering::Equal => return Some(cur),
+                Ordering::Greater => {
+                    pos -= left_size + 1;
+                    cur = right;
+                }
+            }
+        }
+    }
+
+    /// O(log n), the position of slot `idx`, which must be in the tree
+    fn rank(&self, mut idx: usize) -> usize {
+        let mut rank = self.size(self.nodes[idx].left);
+        loop {
+            let parent = self.nodes[idx].parent;
+            if parent == NIL {
+                return rank;
+            }
+            if self.nodes[parent].right == idx {
+                rank += self.size(self.nodes[parent].left) + 1;
+            }
+            idx = parent;
+        }
+    }
+
+    /// O(log n), `prev` and `next` are the list neighbors of the new `idx`
+    fn insert(&mut self, idx: usize, prev: Option<usize>, next: Option<usize>) {
+        if idx >= self.nodes.len() {
+            self.nodes.resize(idx + 1, EMPTY);
+        }
+        let priority = self.next_priority();
+        self.nodes[idx] = Node {
+            size: 1,
+            priority,
+            ..EMPTY
+        };
+
+        /* `next` is the leftmost of the right subtree of `prev` if there is one */
+        let parent = match (prev, next) {
+            (Some(prev), _) if self.nodes[prev].right == NIL => {
+                self.nodes[prev].right = idx;
+                prev
+            }
+            (_, Some(next)) => {
+                debug_assert_eq!(self.nodes[next].left, NIL);
+                self.nodes[next].left = idx;
+                next
+            }
+            _ => {
+                self.root = idx;
+                NIL
+            }
+        };
+        self.nodes[idx].parent = parent;
+
+        let mut cur = parent;
+        while cur != NIL {
+            self.nodes[cur].size += 1;
+            cur = self.nodes[cur].parent;
+        }
+        while self.nodes[idx].parent != NIL
+            && self.nodes[self.nodes[idx].parent].priority < priority
+        {
+            self.rotate_up(idx);
+        }
+    }
+
+    /// O(log n), rotates `idx` down to a leaf and cuts it off
+    fn remove(&mut self, idx: usize) {
+        loop {
+            let Node { left, right, .. } = self.nodes[idx];
+            if left == NIL || right == NIL {
+                break;
+            }
+            if self.nodes[left].priority > self.nodes[right].priority {
+                self.rotate_up(left);
+            } else {
+                self.rotate_up(right);
+            }
+        }
+
+        let Node {
+            left,
+            right,
+            parent,
+            ..
+        } = self.nodes[idx];
+        let child = if left == NIL { right } else { left };
+        if child != NIL {
+            self.nodes[child].parent = parent;
+        }
+        self.replace_child(parent, idx, child);
+
+        let mut cur = parent;
+        while cur != NIL {
+            self.nodes[cur].size -= 1;
+            cur = self.nodes[cur].parent;
+        }
+        self.nodes[idx] = EMPTY;
+    }
+
+    /// Swaps `idx` with its parent, keeping the order
+    fn rotate_up(&mut self, idx: usize) {
+        let parent = self.nodes[idx].parent;
+        let grand = self.nodes[parent].parent;
+
+        let inner = if self.nodes[parent].left == idx {
+            let inner = self.nodes[idx].right;
+            self.nodes[parent].left = inner;
+            self.nodes[idx].right = parent;
+            inner
+        } else {
+            let inner = self.nodes[idx].left;
+            self.nodes[parent].right = inner;
+            self.nodes[idx].left = parent;
+            inner
+        };
+        if inner != NIL {
+            self.nodes[inner].parent = parent;
+        }
+        self.nodes[parent].parent = idx;
+        self.nodes[idx].parent = grand;
+        self.replace_child(grand, parent, idx);
+
+        self.update(parent);
+        self.update(idx);
+    }
+
+    fn replace_child(&mut self, parent: usize, old: usize, new: usize) {
+        if parent == NIL {
+            self.root = new;
+        } else if self.nodes[parent].left == old {
+            self.nodes[parent].left = new;
+        } else {
+            self.nodes[parent].right = new;
+        }
+    }
+
+    fn update(&mut self, idx: usize) {
+        let Node { left, right, .. } = self.nodes[idx];
+        self.nodes[idx].size = self.size(left) + self.size(right) + 1;
+    }
+
+    fn size(&self, idx: usize) -> usize {
+        if idx == NIL {
+            0
+        } else {
+            self.nodes[idx].size
+        }
+    }
+
+    fn next_priority(&mut self) -> u64 {
+        /* xorshift64* */
+        self.seed ^= self.seed >> 12;
+        self.seed ^= self.seed << 25;
+        self.seed ^= self.seed >> 27;
+        self.seed.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+impl<'a, T, S: Storage<T>> Positions<'a, T, S> {
+    /// O(1)
+    pub fn index_at(&self, pos: usize) -> Option<usize> {
+        self.indices.get(pos).copied()
+    }
+
+    /// O(1), `None` if `idx` is deleted
+    pub fn position_of(&self, idx: usize) -> Option<usize> {
+        self.positions.get(idx).copied().flatten()
+    }
+
+    /// O(1)
+    pub fn get(&self, pos: usize) -> Option<&'a T> {
+        self.list.get(self.index_at(pos)?)
+    }
+
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::check;
+
+    /// The treap invariants, and that it matches the list if it's synced
+    fn check_index<T, S: Storage<T>>(list: &VecList<T, S>) {
+        check(list);
+        let Some(index) = list.synced_positions() else {
+            return;
+        };
+
+        fn walk(index: &PositionIndex, idx: usize, parent: usize, order: &mut Vec<usize>) -> usize {
+            if idx == NIL {
+                return 0;
+            }
+            let node = index.nodes[idx];
+            assert_eq!(node.parent, parent);
+            if parent != NIL {
+                assert!(index.nodes[parent].priority >= node.priority);
+            }
+            let left = walk(index, node.left, idx, order);
+            order.push(idx);
+            let right = walk(index, node.right, idx, order);
+            assert_eq!(node.size, left + right + 1);
+            node.size
+        }
+
+        let mut order = Vec::new();
+        walk(index, index.root, NIL, &mut order);
+        let listed: Vec<_> = list.iter().map(|(_, idx)| idx).collect();
+        assert_eq!(order, listed);
+        for (pos, &idx) in listed.iter().enumerate() {
+            assert_eq!(index.select(pos), Some(idx));
+            assert_eq!(index.rank(idx), pos);
+        }
+    }
+
+    fn depth(index: &PositionIndex, idx: usize) -> usize {
+        if idx == NIL {
+            return 0;
+        }
+        let node = index.nodes[idx];
+        1 + depth(index, node.left).max(depth(index, node.right))
+    }
+
+    #[test]
+    fn kept_in_sync_by_push_insert_and_delete() {
+        let mut list = VecList::new();
+        list.set_position_index(true);
+        let mut model: Vec<u32> = Vec::new();
+        let mut state = 7u64;
+        let mut rand = |n: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % n as u64) as usize
+        };
+
+        for round in 0..2000u32 {
+            match rand(6) {
+                0 => {
+                    list.push_back(round);
+                    model.push(round);
+                }
+                1 => {
+                    list.push_front(round);
+                    model.insert(0, round);
+                }
+                2 | 3 => {
+                    let pos = rand(model.len() + 1);
+                    list.insert_at_position(pos, round);
+                    model.insert(pos, round);
+                }
+                _ if !model.is_empty() => {
+                    let pos = rand(model.len());
+                    let idx = list.index_at_position(pos).unwrap();
+                    assert_eq!(list.delete(idx), Some(model.remove(pos)));
+                }
+                _ => {}
+            }
+
+            assert!(list.positions_synced());
+            if round % 50 == 0 {
+                check_index(&list);
+            }
+            let pos = rand(model.len() + 1);
+            let idx = list.index_at_position(pos);
+            assert_eq!(idx.map(|idx| list[idx]), model.get(pos).copied());
+            if let Some(idx) = idx {
+                assert_eq!(list.position_of(idx), Some(pos));
+            }
+        }
+        check_index(&list);
+    }
+
+    #[test]
+    fn other_edits_leave_it_stale() {
+        let mut list: VecList<u32> = (0..10).collect();
+        list.set_position_index(true);
+        check_index(&list);
+
+        list.swap_links(2, 7);
+        assert!(!list.positions_synced());
+        /* a stale index isn't used, the walk still finds the right slots */
+        assert_eq!(list.index_at_position(2), Some(7));
+        assert_eq!(list.position_of(2), Some(7));
+
+        /* and a kept up edit doesn't make it look fresh */
+        list.push_back(10);
+        assert!(!list.positions_synced());
+
+        list.insert_at_position(0, 11);
+        assert!(list.positions_synced());
+        check_index(&list);
+        assert_eq!(list.index_at_position(3), Some(7));
+
+        list.delete_fill(0);
+        assert!(!list.positions_synced());
+        let tail = list.split_at_position(8);
+        assert!(list.positions_synced());
+        check_index(&list);
+        assert_eq!(tail.len(), 3);
+        assert_eq!(
+            list.values().copied().collect::<Vec<_>>(),
+            [11, 1, 7, 3, 4, 5, 6, 2]
+        );
+
+        list.optimize_for_iteration();
+        assert!(list.positions_synced());
+        check_index(&list);
+
+        list.clear();
+        assert!(list.positions_synced() && list.has_position_index());
+        list.push_front(1);
+        list.push_back(2);
+        check_index(&list);
+
+        list.set_position_index(false);
+        assert!(!list.positions_synced());
+        assert_eq!(list.index_at_position(1), Some(1));
+    }
+
+    #[test]
+    fn stays_shallow() {
+        let mut list = VecList::new();
+        list.set_position_index(true);
+        /* the worst order for an unbalanced tree */
+        for i in 0..1 << 15 {
+            list.push_back(i);
+        }
+        for i in 0..1 << 14 {
+            list.insert_at_position(i, i);
+        }
+        let index = list.positions.as_deref().unwrap();
+        assert!(depth(index, index.root) < 80);
+
+        let mut list: VecList<_> = (0..1 << 16).collect();
+        list.set_position_index(true);
+        let index = list.positions.as_deref().unwrap();
+        assert!(depth(index, index.root) < 80);
+        check_index(&list);
+    }
+
+    #[test]
+    fn cloned_with_the_list() {
+        let mut list: VecList<u32> = (0..20).collect();
+        list.set_position_index(true);
+        list.delete(5);
+        let cloned = list.clone();
+        assert!(cloned.positions_synced());
+        check_index(&cloned);
+        assert_eq!(cloned.index_at_position(5), Some(6));
+    }
+}
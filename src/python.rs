@@ -0,0 +1,113 @@
+use pyo3::exceptions::PyIndexError;
+use pyo3::exceptions::PyKeyError;
+use pyo3::prelude::*;
+
+use crate::VecList;
+
+/// `VecList` of Python objects, exposed to Python as `VecList`.
+/// Pushes give handles, which `delete` and `[]` take.
+#[pyclass(name = "VecList", module = "vec_list")]
+#[derive(Default)]
+pub struct PyVecList {
+    list: VecList<Py<PyAny>>,
+}
+
+/// Iterator of a snapshot of the values, from `iter(VecList)`
+#[pyclass(name = "VecListIter", module = "vec_list")]
+pub struct PyVecListIter {
+    vals: std::vec::IntoIter<Py<PyAny>>,
+}
+
+#[pymethods]
+impl PyVecList {
+    #[new]
+    #[pyo3(signature = (iterable = None))]
+    fn py_new(iterable: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let mut list = VecList::new();
+        if let Some(iterable) = iterable {
+            for val in iterable.try_iter()? {
+                list.push_back(val?.unbind());
+            }
+        }
+
+        Ok(Self { list })
+    }
+
+    /// Pushes to the back, returns the handle
+    fn append(&mut self, val: Py<PyAny>) -> usize {
+        self.list.push_back(val)
+    }
+
+    /// Pushes to the front, returns the handle
+    fn appendleft(&mut self, val: Py<PyAny>) -> usize {
+        self.list.push_front(val)
+    }
+
+    fn pop(&mut self) -> PyResult<Py<PyAny>> {
+        self.list
+            .pop_back()
+            .ok_or_else(|| PyIndexError::new_err("pop from an empty VecList"))
+    }
+
+    fn popleft(&mut self) -> PyResult<Py<PyAny>> {
+        self.list
+            .pop_front()
+            .ok_or_else(|| PyIndexError::new_err("pop from an empty VecList"))
+    }
+
+    /// Removes by handle
+    fn delete(&mut self, handle: usize) -> PyResult<Py<PyAny>> {
+        self.list
+            .delete(handle)
+            .ok_or_else(|| PyKeyError::new_err(handle))
+    }
+
+    fn clear(&mut self) {
+        self.list.clear();
+    }
+
+    fn __getitem__(&self, py: Python<'_>, handle: usize) -> PyResult<Py<PyAny>> {
+        self.list
+            .get(handle)
+            .map(|val| val.clone_ref(py))
+            .ok_or_else(|| PyKeyError::new_err(handle))
+    }
+
+    fn __len__(&self) -> usize {
+        self.list.len()
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyVecListIter {
+        let vals: Vec<_> = self.list.values().map(|val| val.clone_ref(py)).collect();
+        PyVecListIter {
+            vals: vals.into_iter(),
+        }
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        let mut reprs = Vec::with_capacity(self.list.len());
+        for val in self.list.values() {
+            reprs.push(val.bind(py).repr()?.to_string());
+        }
+
+        Ok(format!("VecList([{}])", reprs.join(", ")))
+    }
+}
+
+#[pymethods]
+impl PyVecListIter {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<Py<PyAny>> {
+        self.vals.next()
+    }
+}
+
+/// Adds the classes to `module`, call it from the `#[pymodule]` of an extension crate
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyVecList>()?;
+    module.add_class::<PyVecListIter>()?;
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use crate::Values;
+use crate::VecList;
+
+/// A FIFO queue backed by `VecList`, indices are hidden
+#[derive(Debug, Default, Clone)]
+pub struct Queue<T> {
+    list: VecList<T>,
+}
+
+impl<T> Queue<T> {
+    pub const fn new() -> Self {
+        Self {
+            list: VecList::new(),
+        }
+    }
+
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            list: VecList::with_capacity(cap),
+        }
+    }
+
+    /// Average O(1)
+    pub fn enqueue(&mut self, val: T) {
+        self.list.push_back(val);
+    }
+
+    /// O(1)
+    pub fn dequeue(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    /// O(1)
+    pub fn peek(&self) -> Option<&T> {
+        self.list.front().map(|(val, _)| val)
+    }
+
+    /// O(1)
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.list.front_mut().map(|(val, _)| val)
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+
+    /// From front(next to dequeue) to back
+    pub fn iter(&self) -> Values<'_, T> {
+        self.list.values()
+    }
+}
+
+impl<T> IntoIterator for Queue<T> {
+    type Item = T;
+
+    type IntoIter = crate::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Queue<T> {
+    type Item = &'a T;
+
+    type IntoIter = Values<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_in_first_out() {
+        let mut queue = Queue::new();
+        for i in 0..3 {
+            queue.enqueue(i);
+        }
+        assert_eq!(queue.dequeue(), Some(0));
+
+        /* the freed slot is reused, the order stays */
+        queue.enqueue(3);
+        assert!(queue.iter().eq(&[1, 2, 3]));
+        if let Some(front) = queue.peek_mut() {
+            *front += 10;
+        }
+        assert_eq!(queue.peek(), Some(&11));
+
+        let rest: Vec<_> = (0..4).map_while(|_| queue.dequeue()).collect();
+        assert_eq!(rest, [11, 2, 3]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn peek_on_an_empty_queue() {
+        let mut queue = Queue::<u32>::with_capacity(4);
+        assert_eq!(queue.peek(), None);
+        assert_eq!(queue.peek_mut(), None);
+        assert_eq!(queue.dequeue(), None);
+
+        queue.enqueue(1);
+        queue.clear();
+        assert_eq!(queue.peek(), None);
+        assert_eq!(queue.len(), 0);
+    }
+}
@@ -0,0 +1,34 @@
+use std::cmp::Ordering;
+use std::mem::MaybeUninit;
+
+use rayon::slice::ParallelSliceMut;
+
+use crate::Storage;
+use crate::VecList;
+
+impl<T: Send, S: Storage<T>> VecList<T, S> {
+    /// O(n log n), stable, sorts on the rayon pool.
+    /// The list is compacted like `optimize_for_iteration` first, so every index changes.
+    pub fn par_sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.par_sort_by(T::cmp);
+    }
+
+    /// O(n log n), stable, sorts on the rayon pool.
+    /// The list is compacted like `optimize_for_iteration` first, so every index changes.
+    pub fn par_sort_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        self.optimize_for_iteration();
+
+        /* slots `0..len` are linked in order now and all of them have values */
+        let vals = self.storage.parts_mut().1;
+        debug_assert_eq!(vals.len(), self.len);
+        let vals = unsafe { &mut *(vals as *mut [MaybeUninit<T>] as *mut [T]) };
+
+        vals.par_sort_by(compare);
+    }
+}
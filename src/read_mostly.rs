@@ -0,0 +1,71 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use arc_swap::ArcSwap;
+use arc_swap::Guard;
+
+use crate::VecList;
+
+/// A `VecList` for many readers and rare writes, readers never lock or wait:
+/// they get the latest published snapshot, and `update` publishes a new one.
+///
+/// Every `update` clones the list, so batch the writes, e.g. a telemetry list appended
+/// once a second and read by every request.
+#[derive(Debug)]
+pub struct ReadMostly<T> {
+    published: ArcSwap<VecList<T>>,
+    /* one `update` at a time, so none is lost, readers never touch it */
+    writer: Mutex<()>,
+}
+
+impl<T> ReadMostly<T> {
+    pub fn new(list: VecList<T>) -> Self {
+        Self {
+            published: ArcSwap::from_pointee(list),
+            writer: Mutex::new(()),
+        }
+    }
+
+    /// Lock-free, a snapshot which later updates don't change, keep it short lived
+    pub fn read(&self) -> Guard<Arc<VecList<T>>> {
+        self.published.load()
+    }
+
+    /// Lock-free, like `read` but it can be kept around
+    pub fn snapshot(&self) -> Arc<VecList<T>> {
+        self.published.load_full()
+    }
+
+    /// O(n), runs `f` on a copy of the latest list and publishes it,
+    /// indices are kept, so the ones it gives stay valid for later snapshots
+    pub fn update<R, F: FnOnce(&mut VecList<T>) -> R>(&self, f: F) -> R
+    where
+        T: Clone,
+    {
+        let _writer = self.writer.lock().unwrap();
+
+        let mut list = VecList::clone(&self.published.load());
+        let ret = f(&mut list);
+        self.published.store(Arc::new(list));
+        ret
+    }
+
+    /// O(1), publishes `list` as it is
+    pub fn replace(&self, list: VecList<T>) -> Arc<VecList<T>> {
+        let _writer = self.writer.lock().unwrap();
+
+        self.published.swap(Arc::new(list))
+    }
+}
+
+impl<T> Default for ReadMostly<T> {
+    fn default() -> Self {
+        Self::new(VecList::new())
+    }
+}
+
+impl<T> From<VecList<T>> for ReadMostly<T> {
+    fn from(list: VecList<T>) -> Self {
+        Self::new(list)
+    }
+}
@@ -0,0 +1,137 @@
+use std::mem::MaybeUninit;
+
+use crate::Iter;
+use crate::Link;
+use crate::Storage;
+use crate::VecList;
+
+/// Where a front to back iteration stopped, from `Iter::token` or `Resume::token`,
+/// so it can go on later with `VecList::resume`, e.g. a page per frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IterToken {
+    /* `None` if the iteration is done */
+    next: Option<usize>,
+}
+
+/// Iterates from an `IterToken` to the back, from `VecList::resume`
+pub struct Resume<'a, T> {
+    links: &'a [Link],
+    vals: &'a [MaybeUninit<T>],
+    next: Option<usize>,
+}
+
+impl IterToken {
+    pub fn is_done(&self) -> bool {
+        self.next.is_none()
+    }
+
+    /// The index the iteration goes on from
+    pub fn index(&self) -> Option<usize> {
+        self.next
+    }
+}
+
+impl<T, S: Storage<T>> VecList<T, S> {
+    /// O(1), `None` if the element it stopped before was deleted since.
+    /// A slot reused by a new element passes, an index can't tell them apart.
+    pub fn resume(&self, token: IterToken) -> Option<Resume<'_, T>> {
+        if let Some(next) = token.next {
+            self.get(next)?;
+        }
+
+        Some(Resume {
+            links: self.storage.links(),
+            vals: self.storage.vals(),
+            next: token.next,
+        })
+    }
+}
+
+impl<'a, T> Iter<'a, T> {
+    /// O(1), the position of the front end, the back end is not kept
+    pub fn token(&self) -> IterToken {
+        IterToken {
+            next: if self.len == 0 { None } else { self.next },
+        }
+    }
+}
+
+impl<'a, T> Resume<'a, T> {
+    /// O(1)
+    pub fn token(&self) -> IterToken {
+        IterToken { next: self.next }
+    }
+}
+
+impl<'a, T> Iterator for Resume<'a, T> {
+    type Item = (&'a T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next_idx = self.next?;
+
+        let link = unsafe { self.links.get_unchecked(next_idx) };
+
+        debug_assert!(link.has_value());
+
+        self.next = link.next();
+        Some((
+            unsafe { self.vals.get_unchecked(next_idx).assume_init_ref() },
+            next_idx,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rest(list: &VecList<u32>, token: IterToken) -> Option<Vec<u32>> {
+        Some(list.resume(token)?.map(|(val, _)| *val).collect())
+    }
+
+    #[test]
+    fn resumes_where_it_stopped() {
+        let list: VecList<u32> = (0..5).collect();
+        let mut iter = list.iter();
+        iter.next();
+        iter.next();
+        let token = iter.token();
+        assert_eq!(token.index(), Some(2));
+
+        let mut resume = list.resume(token).unwrap();
+        assert_eq!(resume.next(), Some((&2, 2)));
+        assert_eq!(rest(&list, resume.token()), Some(vec![3, 4]));
+
+        let mut iter = list.iter();
+        iter.by_ref().for_each(drop);
+        assert!(iter.token().is_done());
+        assert_eq!(rest(&list, iter.token()), Some(vec![]));
+    }
+
+    #[test]
+    fn a_deleted_slot_stops_it() {
+        let mut list: VecList<u32> = (0..5).collect();
+        let token = IterToken { next: Some(3) };
+        list.delete(3);
+        assert!(list.resume(token).is_none());
+
+        /* also once the slot is gone from the storage */
+        list.delete(4);
+        list.delete_fill(0);
+        assert_eq!(list.cap(), 2);
+        assert!(list.resume(token).is_none());
+    }
+
+    #[test]
+    fn a_reused_slot_resumes_from_the_new_element() {
+        let mut list: VecList<u32> = (0..5).collect();
+        let mut iter = list.iter();
+        iter.nth(1);
+        let token = iter.token();
+
+        list.delete(2);
+        /* the stale token points at the new element, wherever it is in the list */
+        assert_eq!(list.push_front(9), 2);
+        assert_eq!(rest(&list, token), Some(vec![9, 0, 1, 3, 4]));
+    }
+}
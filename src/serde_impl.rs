@@ -0,0 +1,90 @@
+use core::fmt;
+use std::marker::PhantomData;
+
+use serde::de::Error;
+use serde::de::SeqAccess;
+use serde::de::Visitor;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
+use crate::Storage;
+use crate::VecList;
+
+/* slots allocated up front at most, the length hint comes from the input */
+const MAX_PREALLOC: usize = 4096;
+
+/// As a sequence from front to back, indices are not kept
+impl<T: Serialize, S: Storage<T>> Serialize for VecList<T, S> {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        serializer.collect_seq(self.values())
+    }
+}
+
+/// Refills by `push_back`, so indices are `0..len`,
+/// more elements than a fixed storage can hold is an invalid length error
+impl<'de, T: Deserialize<'de>, S: Storage<T>> Deserialize<'de> for VecList<T, S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct VecListVisitor<T, S>(PhantomData<(T, S)>);
+
+        impl<'de, T: Deserialize<'de>, S: Storage<T>> Visitor<'de> for VecListVisitor<T, S> {
+            type Value = VecList<T, S>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a sequence")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let cap = seq.size_hint().unwrap_or(0).min(MAX_PREALLOC);
+                let mut list = VecList::with_storage(S::with_capacity(cap));
+                while let Some(val) = seq.next_element()? {
+                    /* a fixed storage can be given more than it holds */
+                    if list.try_push_back(val).is_err() {
+                        let expected = format!("at most {} elements", list.len());
+                        return Err(A::Error::invalid_length(list.len() + 1, &expected.as_str()));
+                    }
+                }
+                Ok(list)
+            }
+        }
+
+        deserializer.deserialize_seq(VecListVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArrayVecList;
+
+    #[test]
+    fn round_trips_in_list_order() {
+        let mut list: VecList<u32> = (0..6).collect();
+        list.delete(2);
+        list.push_front(10);
+
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(json, "[10,0,1,3,4,5]");
+        let back: VecList<u32> = serde_json::from_str(&json).unwrap();
+        assert!(back.values().eq(list.values()));
+        /* refilled by `push_back` */
+        assert!(back.iter().enumerate().all(|(pos, (_, idx))| pos == idx));
+        crate::tests::check(&back);
+    }
+
+    #[test]
+    fn rejects_what_is_not_a_sequence() {
+        assert!(serde_json::from_str::<VecList<u32>>("{}").is_err());
+        assert!(serde_json::from_str::<VecList<u32>>("[1, \"2\"]").is_err());
+    }
+
+    #[test]
+    fn fills_an_array_storage_up_to_n() {
+        let list: ArrayVecList<u32, 2> = serde_json::from_str("[1,2]").unwrap();
+        assert!(list.values().eq(&[1, 2]));
+
+        let err = serde_json::from_str::<ArrayVecList<u32, 2>>("[1,2,3]").unwrap_err();
+        assert!(err.to_string().contains("at most 2 elements"), "{}", err);
+    }
+}
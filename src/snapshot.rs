@@ -0,0 +1,351 @@
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::mem::MaybeUninit;
+
+use crate::link;
+use crate::Link;
+use crate::SlotReuse;
+use crate::Storage;
+use crate::VecList;
+
+const MAGIC: &[u8; 4] = b"VLST";
+const VERSION: u8 = 1;
+/* `None` of an index */
+const NONE: u64 = u64::MAX;
+
+impl<T, S: Storage<T>> VecList<T, S> {
+    /// O(cap), writes the slot table as it is, deleted slots and the free chain included,
+    /// so `load_state` gives the same indices back, `write_val` writes a value.
+    ///
+    /// After a `VLST` magic, a version byte and a `SlotReuse` byte, numbers are little endian `u64`s,
+    /// and each slot starts with a byte telling if it has a value.
+    pub fn save_state<W, F>(&self, mut writer: W, mut write_val: F) -> io::Result<()>
+    where
+        W: Write,
+        F: FnMut(&mut W, &T) -> io::Result<()>,
+    {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION, self.reuse as u8])?;
+        for num in [self.cap(), self.len] {
+            write_u64(&mut writer, num as u64)?;
+        }
+        for idx in [self.head, self.tail, self.deleted_tail, self.deleted_head] {
+            write_idx(&mut writer, idx)?;
+        }
+
+        for (idx, link) in self.storage.links().iter().enumerate() {
+            if link.has_value() {
+                writer.write_all(&[1])?;
+                write_idx(&mut writer, link.next())?;
+                write_idx(&mut writer, link.prev())?;
+                write_val(&mut writer, unsafe { self.val(idx) })?;
+            } else {
+                writer.write_all(&[0])?;
+                write_idx(&mut writer, link.deleted_prev())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// O(cap), reads what `save_state` wrote, `read_val` reads a value.
+    /// Fails with `InvalidData` if the links don't make up a list and a free chain,
+    /// or if the storage can't hold that many slots.
+    pub fn load_state<R, F>(mut reader: R, mut read_val: F) -> io::Result<Self>
+    where
+        R: Read,
+        F: FnMut(&mut R) -> io::Result<T>,
+    {
+        let mut header = [0; 6];
+        reader.read_exact(&mut header)?;
+        if &header[..4] != MAGIC || header[4] != VERSION {
+            return Err(invalid("not a version 1 list snapshot"));
+        }
+        let reuse = match header[5] {
+            0 => SlotReuse::Lifo,
+            1 => SlotReuse::Fifo,
+            _ => return Err(invalid("bad slot reuse")),
+        };
+
+        let cap = read_u64(&mut reader)? as usize;
+        let len = read_u64(&mut reader)? as usize;
+        let head = read_idx(&mut reader)?;
+        let tail = read_idx(&mut reader)?;
+        let deleted_tail = read_idx(&mut reader)?;
+        let deleted_head = read_idx(&mut reader)?;
+
+        let storage = S::with_capacity(0);
+        if cap > storage.max_capacity().min(link::MAX_SLOTS) || len > cap {
+            return Err(invalid("too many slots"));
+        }
+
+        let mut links = Vec::new();
+        let mut vals = Vec::new();
+        for _ in 0..cap {
+            let mut tag = [0];
+            reader.read_exact(&mut tag)?;
+            match tag[0] {
+                1 => {
+                    let next = read_idx(&mut reader)?;
+                    let prev = read_idx(&mut reader)?;
+                    links.push(Link::value(next, prev));
+                    vals.push(Some(read_val(&mut reader)?));
+                }
+                0 => {
+                    links.push(Link::deleted(read_idx(&mut reader)?));
+                    vals.push(None);
+                }
+                _ => return Err(invalid("bad slot tag")),
+            }
+        }
+
+        check_chains(&links, len, [head, tail, deleted_tail, deleted_head])?;
+
+        let mut list = Self::with_storage(storage);
+        list.storage.reserve(cap);
+        for (link, val) in links.into_iter().zip(vals) {
+            let val = val.map_or(MaybeUninit::uninit(), MaybeUninit::new);
+            list.storage.push(link, val);
+        }
+        list.head = head;
+        list.tail = tail;
+        list.deleted_tail = deleted_tail;
+        list.deleted_head = deleted_head;
+        list.reuse = reuse;
+        list.len = len;
+        list.contiguous = len == cap && list.iter().enumerate().all(|(pos, (_, idx))| pos == idx);
+
+        Ok(list)
+    }
+}
+
+/// Whether the value slots make up exactly one list of `len` from `head` to `tail`,
+/// and the deleted ones exactly one chain from `deleted_tail` to `deleted_head`
+fn check_chains(links: &[Link], len: usize, ends: [Option<usize>; 4]) -> io::Result<()> {
+    let [head, tail, deleted_tail, deleted_head] = ends;
+    let in_range = |idx: Option<usize>| idx.is_none_or(|idx| idx < links.len());
+    let all_in_range = [head, tail, deleted_tail, deleted_head]
+        .into_iter()
+        .all(in_range)
+        && links.iter().all(|link| match link.has_value() {
+            true => in_range(link.next()) && in_range(link.prev()),
+            false => in_range(link.deleted_prev()),
+        });
+    if !all_in_range {
+        return Err(invalid("index out of range"));
+    }
+
+    let mut seen = vec![false; links.len()];
+    let mut walk = |from: Option<usize>, live: bool, step: fn(&Link) -> Option<usize>| {
+        let (mut cur, mut last, mut count) = (from, None, 0);
+        while let Some(idx) = cur {
+            if seen[idx] || links[idx].has_value() != live {
+                return None;
+            }
+            seen[idx] = true;
+            (cur, last, count) = (step(&links[idx]), Some(idx), count + 1);
+        }
+        Some((last, count))
+    };
+
+    let ok = walk(head, true, Link::next) == Some((tail, len))
+        && walk(deleted_tail, false, Link::deleted_prev) == Some((deleted_head, links.len() - len))
+        && head.is_none_or(|head| links[head].prev().is_none())
+        && links.iter().enumerate().all(|(idx, link)| {
+            !link.has_value()
+                || link
+                    .next()
+                    .is_none_or(|next| links[next].prev() == Some(idx))
+        });
+    if ok {
+        Ok(())
+    } else {
+        Err(invalid("broken links"))
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn write_u64<W: Write>(writer: &mut W, num: u64) -> io::Result<()> {
+    writer.write_all(&num.to_le_bytes())
+}
+
+fn write_idx<W: Write>(writer: &mut W, idx: Option<usize>) -> io::Result<()> {
+    write_u64(writer, idx.map_or(NONE, |idx| idx as u64))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_idx<R: Read>(reader: &mut R) -> io::Result<Option<usize>> {
+    match read_u64(reader)? {
+        NONE => Ok(None),
+        idx => usize::try_from(idx)
+            .ok()
+            .filter(|&idx| idx < link::MAX_SLOTS)
+            .map(Some)
+            .ok_or_else(|| invalid("index out of range")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::check;
+    use crate::ArrayStorage;
+    use crate::ArrayVecList;
+    use crate::VecStorage;
+
+    const HEADER: usize = 6 + 6 * 8;
+
+    fn save(list: &VecList<u32>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        list.save_state(&mut bytes, |w, val| w.write_all(&val.to_le_bytes()))
+            .unwrap();
+        bytes
+    }
+
+    fn load<S: Storage<u32>>(bytes: &[u8]) -> io::Result<VecList<u32, S>> {
+        VecList::load_state(bytes, |r| {
+            let mut val = [0; 4];
+            r.read_exact(&mut val)?;
+            Ok(u32::from_le_bytes(val))
+        })
+    }
+
+    fn fragmented(reuse: SlotReuse) -> VecList<u32> {
+        let mut list: VecList<u32> = (0..8).collect();
+        list.set_slot_reuse(reuse);
+        list.delete(2);
+        list.delete(6);
+        list.delete(0);
+        list.push_front(10);
+        list.swap_links(1, 7);
+        list
+    }
+
+    fn invalid_data(bytes: &[u8]) -> String {
+        let err = load::<VecStorage<u32>>(bytes).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        err.to_string()
+    }
+
+    #[test]
+    fn round_trip_keeps_slots() {
+        for reuse in [SlotReuse::Lifo, SlotReuse::Fifo] {
+            let mut list = fragmented(reuse);
+            let mut loaded: VecList<u32> = load(&save(&list)).unwrap();
+            check(&loaded);
+            assert_eq!(loaded.slot_reuse(), reuse);
+            assert_eq!(loaded.cap(), list.cap());
+            assert!(list.iter().eq(loaded.iter()));
+
+            /* the free chain comes back in the same order */
+            for val in 20..22 {
+                assert_eq!(loaded.push_back(val), list.push_back(val));
+            }
+            check(&loaded);
+        }
+    }
+
+    #[test]
+    fn round_trip_contiguous() {
+        let list: VecList<u32> = (0..5).collect();
+        let loaded: VecList<u32> = load(&save(&list)).unwrap();
+        check(&loaded);
+        assert!(loaded.is_contiguous());
+
+        let empty: VecList<u32> = load(&save(&VecList::new())).unwrap();
+        check(&empty);
+        assert!(empty.is_empty() && empty.is_contiguous());
+    }
+
+    #[test]
+    fn rejects_bad_headers() {
+        let bytes = save(&fragmented(SlotReuse::Lifo));
+
+        let mut bad = bytes.clone();
+        bad[0] = b'X';
+        assert_eq!(invalid_data(&bad), "not a version 1 list snapshot");
+
+        let mut bad = bytes.clone();
+        bad[4] = VERSION + 1;
+        assert_eq!(invalid_data(&bad), "not a version 1 list snapshot");
+
+        let mut bad = bytes.clone();
+        bad[5] = 2;
+        assert_eq!(invalid_data(&bad), "bad slot reuse");
+
+        let mut bad = bytes.clone();
+        bad[HEADER] = 2;
+        assert_eq!(invalid_data(&bad), "bad slot tag");
+
+        let err = load::<VecStorage<u32>>(&bytes[..bytes.len() - 1]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn rejects_broken_links() {
+        let list: VecList<u32> = (0..4).collect();
+        let bytes = save(&list);
+        /* a value slot is a tag, `next`, `prev` and a 4 byte value */
+        let next_of = |slot: usize| HEADER + slot * 21 + 1;
+        let set = |bytes: &mut [u8], at: usize, num: u64| {
+            bytes[at..at + 8].copy_from_slice(&num.to_le_bytes())
+        };
+
+        let mut bad = bytes.clone();
+        set(&mut bad, next_of(1), 1);
+        assert_eq!(invalid_data(&bad), "broken links");
+
+        let mut bad = bytes.clone();
+        set(&mut bad, next_of(1), 3);
+        assert_eq!(invalid_data(&bad), "broken links");
+
+        let mut bad = bytes.clone();
+        set(&mut bad, next_of(2), 9);
+        assert_eq!(invalid_data(&bad), "index out of range");
+
+        /* the len field */
+        let mut bad = bytes.clone();
+        set(&mut bad, 14, 3);
+        assert_eq!(invalid_data(&bad), "broken links");
+        set(&mut bad, 14, 5);
+        assert_eq!(invalid_data(&bad), "too many slots");
+
+        /* the head field */
+        let mut bad = bytes.clone();
+        set(&mut bad, 22, 1);
+        assert_eq!(invalid_data(&bad), "broken links");
+    }
+
+    #[test]
+    fn rejects_a_free_chain_through_values() {
+        let mut list: VecList<u32> = (0..4).collect();
+        list.delete(1);
+        let mut bytes = save(&list);
+        /* the deleted tail field points at a value slot */
+        bytes[38..46].copy_from_slice(&0u64.to_le_bytes());
+        assert_eq!(invalid_data(&bytes), "broken links");
+    }
+
+    #[test]
+    fn fixed_storage_too_small() {
+        let bytes = save(&(0..5).collect());
+        let err = load::<ArrayStorage<u32, 4>>(&bytes).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let loaded: ArrayVecList<u32, 5> = load(&bytes).unwrap();
+        check(&loaded);
+        assert_eq!(
+            loaded.values().copied().collect::<Vec<_>>(),
+            [0, 1, 2, 3, 4]
+        );
+    }
+}
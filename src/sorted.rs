@@ -0,0 +1,190 @@
+use std::ops::Bound;
+use std::ops::RangeBounds;
+
+use crate::Iter;
+use crate::Values;
+use crate::VecList;
+
+/// A `VecList` which is always sorted, the equal ones are in insertion order.
+/// The indices are stable handles, like `VecList`'s.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct SortedList<T> {
+    list: VecList<T>,
+}
+
+pub struct SortedRange<'a, T, R> {
+    iter: Iter<'a, T>,
+    range: R,
+    /* past the end, nothing more is in range */
+    done: bool,
+}
+
+impl<T: Ord> SortedList<T> {
+    pub const fn new() -> Self {
+        Self {
+            list: VecList::new(),
+        }
+    }
+
+    /// O(n), O(1) when values come in order
+    pub fn insert(&mut self, val: T) -> usize {
+        self.list.insert_sorted(val)
+    }
+
+    /// O(1)
+    pub fn remove(&mut self, idx: usize) -> Option<T> {
+        self.list.delete(idx)
+    }
+
+    /// O(n), the earliest inserted one of the equal ones
+    pub fn remove_first(&mut self, val: &T) -> Option<T> {
+        let idx = self
+            .list
+            .iter()
+            .take_while(|(x, _)| *x <= val)
+            .find(|(x, _)| *x == val)
+            .map(|(_, idx)| idx)?;
+
+        self.list.delete(idx)
+    }
+
+    /// O(1)
+    pub fn first(&self) -> Option<(&T, usize)> {
+        self.list.front()
+    }
+
+    /// O(1)
+    pub fn last(&self) -> Option<(&T, usize)> {
+        self.list.back()
+    }
+
+    /// O(1)
+    pub fn pop_first(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    /// O(1)
+    pub fn pop_last(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        self.list.get(idx)
+    }
+
+    /// O(n)
+    pub fn contains(&self, val: &T) -> bool {
+        self.list
+            .values()
+            .take_while(|x| *x <= val)
+            .any(|x| x == val)
+    }
+
+    /// The ones in `range` in order, stops at the first one past it
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> SortedRange<'_, T, R> {
+        SortedRange {
+            iter: self.list.iter(),
+            range,
+            done: false,
+        }
+    }
+
+    /// O(n + m), the ones of `other` go after the equal ones of `self`, their indices are not kept
+    pub fn merge(&mut self, other: SortedList<T>) {
+        let mut cur = self.list.front().map(|(_, idx)| idx);
+        for val in other.list {
+            while let Some(idx) = cur {
+                if self.list[idx] > val {
+                    break;
+                }
+                cur = self.list.next(idx);
+            }
+
+            match cur {
+                Some(idx) => self.list.insert_before(idx, val),
+                None => self.list.push_back(val),
+            };
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+
+    /// Smallest first
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.list.iter()
+    }
+
+    /// Smallest first
+    pub fn values(&self) -> Values<'_, T> {
+        self.list.values()
+    }
+
+    pub fn as_list(&self) -> &VecList<T> {
+        &self.list
+    }
+
+    pub fn into_list(self) -> VecList<T> {
+        self.list
+    }
+}
+
+impl<T: Ord> FromIterator<T> for SortedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vals: Vec<T> = iter.into_iter().collect();
+        vals.sort();
+        Self {
+            list: vals.into_iter().collect(),
+        }
+    }
+}
+
+impl<T: Ord> Extend<T> for SortedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.merge(iter.into_iter().collect());
+    }
+}
+
+impl<'a, T: Ord, R: RangeBounds<T>> Iterator for SortedRange<'a, T, R> {
+    type Item = (&'a T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        for (val, idx) in self.iter.by_ref() {
+            let after_start = match self.range.start_bound() {
+                Bound::Included(start) => val >= start,
+                Bound::Excluded(start) => val > start,
+                Bound::Unbounded => true,
+            };
+            if !after_start {
+                continue;
+            }
+
+            let before_end = match self.range.end_bound() {
+                Bound::Included(end) => val <= end,
+                Bound::Excluded(end) => val < end,
+                Bound::Unbounded => true,
+            };
+            if !before_end {
+                break;
+            }
+
+            return Some((val, idx));
+        }
+
+        self.done = true;
+        None
+    }
+}
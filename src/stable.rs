@@ -0,0 +1,160 @@
+use crate::LendingIterator;
+use crate::Storage;
+use crate::VecList;
+
+/// Front to back over the elements there were at the start, pushing, inserting after the
+/// current one and deleting it are allowed in between, from `VecList::iter_stable`
+pub struct IterStable<'a, T, S: Storage<T>> {
+    list: &'a mut VecList<T, S>,
+    next: Option<usize>,
+    /* the one lent out last, if it's still there */
+    current: Option<usize>,
+    /* the tail at the start, nothing after it is visited */
+    last: Option<usize>,
+}
+
+impl<T, S: Storage<T>> VecList<T, S> {
+    /// Lends `(&mut T, idx)`s up to the tail as it is now, what's pushed meanwhile isn't visited.
+    /// It only keeps indices, so the slots may grow and move while iterating.
+    pub fn iter_stable(&mut self) -> IterStable<'_, T, S> {
+        IterStable {
+            next: self.head,
+            current: None,
+            last: self.tail,
+            list: self,
+        }
+    }
+}
+
+impl<'a, T, S: Storage<T>> IterStable<'a, T, S> {
+    /// Average O(1), it won't be visited
+    pub fn push_back(&mut self, val: T) -> usize {
+        self.list.push_back(val)
+    }
+
+    /// Average O(1), it won't be visited
+    pub fn push_front(&mut self, val: T) -> usize {
+        self.list.push_front(val)
+    }
+
+    /// Average O(1), links `val` right after the one lent out last, it won't be visited.
+    /// Panics if there's none, before the first `next` or after `delete_current`.
+    pub fn insert_after_current(&mut self, val: T) -> usize {
+        let idx = self.current.expect("no current element!");
+        self.list.insert_after(idx, val)
+    }
+
+    /// O(1), deletes the one lent out last, the iteration goes on after it
+    pub fn delete_current(&mut self) -> Option<T> {
+        let idx = self.current.take()?;
+        self.list.delete(idx)
+    }
+
+    pub fn list(&self) -> &VecList<T, S> {
+        self.list
+    }
+}
+
+impl<'a, T, S: Storage<T>> LendingIterator for IterStable<'a, T, S> {
+    type Item<'b>
+        = (&'b mut T, usize)
+    where
+        Self: 'b;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        let idx = self.next?;
+
+        /* the next one is taken now, so what's inserted after this one or deleting it can't
+        change the walk, and only the links in front of it change meanwhile */
+        self.current = Some(idx);
+        self.next = if Some(idx) == self.last {
+            None
+        } else {
+            self.list.next(idx)
+        };
+
+        Some((unsafe { self.list.val_mut(idx) }, idx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* visits each one, `f` gets the value and may change the list */
+    fn walk<F: FnMut(&mut IterStable<'_, u32, crate::VecStorage<u32>>, u32)>(
+        list: &mut VecList<u32>,
+        mut f: F,
+    ) -> Vec<u32> {
+        let mut visited = Vec::new();
+        let mut iter = list.iter_stable();
+        while let Some((val, _)) = iter.next() {
+            let val = *val;
+            visited.push(val);
+            f(&mut iter, val);
+        }
+        visited
+    }
+
+    #[test]
+    fn pushed_ones_are_not_visited() {
+        let mut list: VecList<u32> = (0..3).collect();
+        let visited = walk(&mut list, |iter, val| {
+            /* enough to grow the storage a few times */
+            for i in 0..10 {
+                iter.push_back(10 * val + i + 10);
+            }
+            iter.push_front(100 + val);
+        });
+        assert_eq!(visited, [0, 1, 2]);
+        assert_eq!(list.len(), 36);
+        assert_eq!(list.front(), Some((&102, 35)));
+        crate::tests::check(&list);
+    }
+
+    #[test]
+    fn inserted_after_the_current_one_are_skipped() {
+        let mut list: VecList<u32> = (0..3).collect();
+        let visited = walk(&mut list, |iter, val| {
+            iter.insert_after_current(val + 10);
+        });
+        assert_eq!(visited, [0, 1, 2]);
+        assert!(list.values().eq(&[0, 10, 1, 11, 2, 12]));
+        crate::tests::check(&list);
+    }
+
+    #[test]
+    fn deleting_the_current_one_goes_on_after_it() {
+        let mut list: VecList<u32> = (0..6).collect();
+        let visited = walk(&mut list, |iter, val| {
+            if val % 2 == 0 {
+                assert_eq!(iter.delete_current(), Some(val));
+                assert_eq!(iter.delete_current(), None);
+                /* reuses the slot just freed, still not visited */
+                iter.push_back(val + 10);
+            }
+        });
+        assert_eq!(visited, [0, 1, 2, 3, 4, 5]);
+        assert!(list.values().eq(&[1, 3, 5, 10, 12, 14]));
+        crate::tests::check(&list);
+
+        /* the tail at the start is deleted */
+        let mut list: VecList<u32> = (0..2).collect();
+        let visited = walk(&mut list, |iter, _| {
+            iter.delete_current();
+            iter.push_back(7);
+        });
+        assert_eq!(visited, [0, 1]);
+        assert!(list.values().eq(&[7, 7]));
+    }
+
+    #[test]
+    #[should_panic(expected = "no current element!")]
+    fn inserting_after_a_deleted_current_panics() {
+        let mut list: VecList<u32> = (0..2).collect();
+        walk(&mut list, |iter, _| {
+            iter.delete_current();
+            iter.insert_after_current(9);
+        });
+    }
+}
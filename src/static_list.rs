@@ -0,0 +1,67 @@
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+
+use crate::ArrayVecList;
+
+/// An `ArrayVecList` to share through a `static`, interrupt handlers included.
+/// Every call is one short critical section, nothing ever blocks or allocates.
+///
+/// It's the usual driver event queue: the handler pushes, the main loop pops.
+/// The target has to provide a `critical-section` implementation.
+pub struct StaticVecList<T, const N: usize> {
+    list: Mutex<RefCell<ArrayVecList<T, N>>>,
+}
+
+impl<T, const N: usize> StaticVecList<T, N> {
+    pub const fn new() -> Self {
+        Self {
+            list: Mutex::new(RefCell::new(ArrayVecList::new_inline())),
+        }
+    }
+
+    /// O(1), gives the value back if all `N` slots are taken
+    pub fn try_push_back(&self, val: T) -> Result<usize, T> {
+        self.with(|list| list.push_back_within_capacity(val))
+    }
+
+    /// O(1), gives the value back if all `N` slots are taken
+    pub fn try_push_front(&self, val: T) -> Result<usize, T> {
+        self.with(|list| list.push_front_within_capacity(val))
+    }
+
+    /// O(1)
+    pub fn try_pop_front(&self) -> Option<T> {
+        self.with(|list| list.pop_front())
+    }
+
+    /// O(1)
+    pub fn try_pop_back(&self) -> Option<T> {
+        self.with(|list| list.pop_back())
+    }
+
+    /// O(1)
+    pub fn delete(&self, idx: usize) -> Option<T> {
+        self.with(|list| list.delete(idx))
+    }
+
+    pub fn len(&self) -> usize {
+        self.with(|list| list.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Runs `f` on the list inside one critical section, keep it short.
+    /// Panics if `f` reaches this list again.
+    pub fn with<R, F: FnOnce(&mut ArrayVecList<T, N>) -> R>(&self, f: F) -> R {
+        critical_section::with(|cs| f(&mut self.list.borrow_ref_mut(cs)))
+    }
+}
+
+impl<T, const N: usize> Default for StaticVecList<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
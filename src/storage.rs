@@ -0,0 +1,328 @@
+use std::mem;
+use std::mem::ManuallyDrop;
+use std::mem::MaybeUninit;
+use std::slice;
+
+use crate::Link;
+use crate::VecListError;
+
+/// Where a `VecList` keeps its slots: two parallel arrays of links and values,
+/// which only grow at the end and are emptied all at once.
+///
+/// The list owns the values, a storage never drops them.
+///
+/// # Safety
+/// `links`, `vals` and `parts_mut` must always give slices of the same length,
+/// which grows by exactly one on each `push`, goes back to `len` on `truncate` and to 0 on `clear`,
+/// and `capacity` must never be less than that length.
+pub unsafe trait Storage<T> {
+    /// A fixed storage may ignore `cap`
+    fn with_capacity(cap: usize) -> Self;
+
+    fn capacity(&self) -> usize;
+
+    /// How many slots it can ever hold
+    fn max_capacity(&self) -> usize {
+        usize::MAX
+    }
+
+    /// Panics if the storage is fixed and full
+    fn push(&mut self, link: Link, val: MaybeUninit<T>);
+
+    /// The value slots from the slot count up to the capacity
+    fn spare_vals_mut(&mut self) -> &mut [MaybeUninit<T>];
+
+    /// Like `push`, but the value is whatever the first spare value slot holds
+    ///
+    /// # Safety
+    /// There must be a spare slot.
+    unsafe fn push_link(&mut self, link: Link);
+
+    /// A hint only
+    fn reserve(&mut self, additional: usize);
+
+    /// Makes room for `additional` more slots or fails with `CapacityExceeded`,
+    /// if that's more than `max_capacity` or the allocation fails
+    fn try_reserve(&mut self, additional: usize) -> Result<(), VecListError> {
+        match self.links().len().checked_add(additional) {
+            Some(len) if len <= self.max_capacity() => {
+                self.reserve(additional);
+                Ok(())
+            }
+            _ => Err(VecListError::CapacityExceeded),
+        }
+    }
+
+    /// A hint only, the capacity never goes below the slot count
+    fn shrink_to(&mut self, _min_capacity: usize) {}
+
+    /// Forgets all slots without dropping the values
+    fn clear(&mut self);
+
+    /// Forgets the slots from `len` on without dropping the values, `len` is at most the slot count
+    fn truncate(&mut self, len: usize);
+
+    fn links(&self) -> &[Link];
+
+    fn vals(&self) -> &[MaybeUninit<T>];
+
+    fn parts_mut(&mut self) -> (&mut [Link], &mut [MaybeUninit<T>]);
+}
+
+/// How a `VecStorage` grows when a push finds it full
+#[derive(Debug, Default, Clone, Copy)]
+pub enum Growth {
+    /// Like `Vec`, amortized O(1) pushes
+    #[default]
+    Double,
+    /// One more slot each time, no spare memory but O(n) pushes when growing
+    Exact,
+    /// This many more slots each time, at least one
+    Add(usize),
+    /// Maps the full capacity to the new one, anything not above it means one more slot
+    Custom(fn(usize) -> usize),
+}
+
+/// Two growable `Vec`s, the default storage
+pub struct VecStorage<T> {
+    links: Vec<Link>,
+    vals: Vec<MaybeUninit<T>>,
+    growth: Growth,
+}
+
+impl<T> VecStorage<T> {
+    pub const fn new() -> Self {
+        Self::with_growth(Growth::Double)
+    }
+
+    pub const fn with_growth(growth: Growth) -> Self {
+        Self {
+            links: Vec::new(),
+            vals: Vec::new(),
+            growth,
+        }
+    }
+
+    pub fn growth(&self) -> Growth {
+        self.growth
+    }
+
+    /// Used from the next time it's full on
+    pub fn set_growth(&mut self, growth: Growth) {
+        self.growth = growth;
+    }
+
+    /// Empties it into a storage of `U`, the values are forgotten.
+    /// The value buffer is kept only if `T` and `U` have the same size and alignment.
+    pub(crate) fn recycle<U>(self) -> VecStorage<U> {
+        let VecStorage {
+            mut links,
+            vals,
+            growth,
+        } = self;
+        links.clear();
+
+        let vals = if mem::size_of::<T>() == mem::size_of::<U>()
+            && mem::align_of::<T>() == mem::align_of::<U>()
+        {
+            let mut vals = ManuallyDrop::new(vals);
+            /* same layout, so the buffer can be freed as a buffer of `U` */
+            unsafe { Vec::from_raw_parts(vals.as_mut_ptr().cast(), 0, vals.capacity()) }
+        } else {
+            Vec::new()
+        };
+
+        VecStorage {
+            links,
+            vals,
+            growth,
+        }
+    }
+
+    /// Reserves by `growth`, a `Double` one is left to `Vec`
+    fn grow(&mut self) {
+        let cap = self.capacity();
+        let new_cap = match self.growth {
+            Growth::Double => return,
+            Growth::Exact => cap + 1,
+            Growth::Add(n) => cap.saturating_add(n.max(1)),
+            Growth::Custom(f) => f(cap).max(cap + 1),
+        };
+
+        let additional = new_cap - self.links.len();
+        self.links.reserve_exact(additional);
+        self.vals.reserve_exact(additional);
+    }
+}
+
+unsafe impl<T> Storage<T> for VecStorage<T> {
+    fn with_capacity(cap: usize) -> Self {
+        Self {
+            links: Vec::with_capacity(cap),
+            vals: Vec::with_capacity(cap),
+            growth: Growth::Double,
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.links.capacity().min(self.vals.capacity())
+    }
+
+    fn push(&mut self, link: Link, val: MaybeUninit<T>) {
+        if self.links.len() == self.capacity() {
+            self.grow();
+        }
+
+        self.links.push(link);
+        self.vals.push(val);
+    }
+
+    fn spare_vals_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        let (len, spare) = (self.vals.len(), self.capacity() - self.vals.len());
+        /* `MaybeUninit<T>` is fine uninitialized */
+        unsafe { slice::from_raw_parts_mut(self.vals.as_mut_ptr().add(len), spare) }
+    }
+
+    unsafe fn push_link(&mut self, link: Link) {
+        debug_assert!(self.links.len() < self.capacity());
+
+        self.links.push(link);
+        self.vals.set_len(self.vals.len() + 1);
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        if matches!(self.growth, Growth::Double) {
+            self.links.reserve(additional);
+            self.vals.reserve(additional);
+        } else {
+            self.links.reserve_exact(additional);
+            self.vals.reserve_exact(additional);
+        }
+    }
+
+    fn try_reserve(&mut self, additional: usize) -> Result<(), VecListError> {
+        let reserved = if matches!(self.growth, Growth::Double) {
+            self.links
+                .try_reserve(additional)
+                .and_then(|_| self.vals.try_reserve(additional))
+        } else {
+            self.links
+                .try_reserve_exact(additional)
+                .and_then(|_| self.vals.try_reserve_exact(additional))
+        };
+        reserved.map_err(|_| VecListError::CapacityExceeded)
+    }
+
+    fn shrink_to(&mut self, min_capacity: usize) {
+        self.links.shrink_to(min_capacity);
+        self.vals.shrink_to(min_capacity);
+    }
+
+    fn clear(&mut self) {
+        self.links.clear();
+        self.vals.clear();
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.links.truncate(len);
+        self.vals.truncate(len);
+    }
+
+    fn links(&self) -> &[Link] {
+        &self.links
+    }
+
+    fn vals(&self) -> &[MaybeUninit<T>] {
+        &self.vals
+    }
+
+    fn parts_mut(&mut self) -> (&mut [Link], &mut [MaybeUninit<T>]) {
+        (&mut self.links, &mut self.vals)
+    }
+}
+
+/// Two inline arrays of `N` slots, never allocates
+pub struct ArrayStorage<T, const N: usize> {
+    links: [Link; N],
+    vals: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> ArrayStorage<T, N> {
+    pub const fn new() -> Self {
+        Self {
+            links: [Link::deleted(None); N],
+            vals: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        }
+    }
+}
+
+unsafe impl<T, const N: usize> Storage<T> for ArrayStorage<T, N> {
+    fn with_capacity(_cap: usize) -> Self {
+        Self::new()
+    }
+
+    fn capacity(&self) -> usize {
+        N
+    }
+
+    fn max_capacity(&self) -> usize {
+        N
+    }
+
+    fn push(&mut self, link: Link, val: MaybeUninit<T>) {
+        assert!(self.len < N, "storage is full!");
+
+        self.links[self.len] = link;
+        self.vals[self.len] = val;
+        self.len += 1;
+    }
+
+    fn spare_vals_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        &mut self.vals[self.len..]
+    }
+
+    unsafe fn push_link(&mut self, link: Link) {
+        debug_assert!(self.len < N);
+
+        *self.links.get_unchecked_mut(self.len) = link;
+        self.len += 1;
+    }
+
+    fn reserve(&mut self, _additional: usize) {}
+
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    fn truncate(&mut self, len: usize) {
+        debug_assert!(len <= self.len);
+
+        self.len = len;
+    }
+
+    fn links(&self) -> &[Link] {
+        &self.links[..self.len]
+    }
+
+    fn vals(&self) -> &[MaybeUninit<T>] {
+        &self.vals[..self.len]
+    }
+
+    fn parts_mut(&mut self) -> (&mut [Link], &mut [MaybeUninit<T>]) {
+        (&mut self.links[..self.len], &mut self.vals[..self.len])
+    }
+}
+
+impl<T> Default for VecStorage<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Default for ArrayStorage<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
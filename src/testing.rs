@@ -0,0 +1,486 @@
+use core::fmt;
+use std::collections::VecDeque;
+use std::mem::MaybeUninit;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use crate::Link;
+use crate::Storage;
+use crate::VecList;
+use crate::VecListError;
+use crate::VecStorage;
+
+/// One step of a differential test, positions are taken modulo the len, so any sequence is valid
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Op<T> {
+    PushBack(T),
+    PushFront(T),
+    PopFront,
+    PopBack,
+    /// After the element at this position
+    InsertAfter(usize, T),
+    /// Before the element at this position
+    InsertBefore(usize, T),
+    /// The element at this position
+    Delete(usize),
+    Clear,
+}
+
+/// What the harness drives, implement it for a layer on top of `VecList` to test that layer.
+/// Indices are whatever the subject hands out, the model only checks they stay put.
+pub trait Subject<T> {
+    fn push_back(&mut self, val: T) -> usize;
+    fn push_front(&mut self, val: T) -> usize;
+    fn pop_front(&mut self) -> Option<T>;
+    fn pop_back(&mut self) -> Option<T>;
+    fn insert_after(&mut self, idx: usize, val: T) -> usize;
+    fn insert_before(&mut self, idx: usize, val: T) -> usize;
+    fn delete(&mut self, idx: usize) -> Option<T>;
+    fn clear(&mut self);
+    /// Front to back
+    fn entries(&self) -> Vec<(&T, usize)>;
+}
+
+/// The reference, the values with their indices in a `VecDeque`
+#[derive(Debug, Clone)]
+pub struct Model<T> {
+    vals: VecDeque<(T, usize)>,
+}
+
+impl<T, S: Storage<T>> Subject<T> for VecList<T, S> {
+    fn push_back(&mut self, val: T) -> usize {
+        VecList::push_back(self, val)
+    }
+
+    fn push_front(&mut self, val: T) -> usize {
+        VecList::push_front(self, val)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        VecList::pop_front(self)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        VecList::pop_back(self)
+    }
+
+    fn insert_after(&mut self, idx: usize, val: T) -> usize {
+        VecList::insert_after(self, idx, val)
+    }
+
+    fn insert_before(&mut self, idx: usize, val: T) -> usize {
+        VecList::insert_before(self, idx, val)
+    }
+
+    fn delete(&mut self, idx: usize) -> Option<T> {
+        VecList::delete(self, idx)
+    }
+
+    fn clear(&mut self) {
+        VecList::clear(self);
+    }
+
+    fn entries(&self) -> Vec<(&T, usize)> {
+        self.iter().collect()
+    }
+}
+
+impl<T: Clone + PartialEq + fmt::Debug> Model<T> {
+    pub const fn new() -> Self {
+        Self {
+            vals: VecDeque::new(),
+        }
+    }
+
+    /// Applies `op` to both and compares what they give back.
+    /// Panics on the first difference.
+    pub fn apply<U: Subject<T>>(&mut self, subject: &mut U, op: Op<T>) {
+        match op {
+            Op::PushBack(val) => {
+                let idx = subject.push_back(val.clone());
+                self.vals.push_back((val, idx));
+            }
+            Op::PushFront(val) => {
+                let idx = subject.push_front(val.clone());
+                self.vals.push_front((val, idx));
+            }
+            Op::PopFront => {
+                let expected = self.vals.pop_front().map(|(val, _)| val);
+                assert_eq!(subject.pop_front(), expected, "pop_front differs!");
+            }
+            Op::PopBack => {
+                let expected = self.vals.pop_back().map(|(val, _)| val);
+                assert_eq!(subject.pop_back(), expected, "pop_back differs!");
+            }
+            Op::InsertAfter(pos, val) => match self.at(pos) {
+                Some((pos, at)) => {
+                    let idx = subject.insert_after(at, val.clone());
+                    self.vals.insert(pos + 1, (val, idx));
+                }
+                None => self.apply(subject, Op::PushBack(val)),
+            },
+            Op::InsertBefore(pos, val) => match self.at(pos) {
+                Some((pos, at)) => {
+                    let idx = subject.insert_before(at, val.clone());
+                    self.vals.insert(pos, (val, idx));
+                }
+                None => self.apply(subject, Op::PushFront(val)),
+            },
+            Op::Delete(pos) => {
+                if let Some((pos, at)) = self.at(pos) {
+                    let expected = self.vals.remove(pos).map(|(val, _)| val);
+                    assert_eq!(subject.delete(at), expected, "delete differs!");
+                }
+            }
+            Op::Clear => {
+                self.vals.clear();
+                subject.clear();
+            }
+        }
+
+        self.check(subject);
+    }
+
+    /// Applies every op in order
+    pub fn run<U: Subject<T>, I: IntoIterator<Item = Op<T>>>(&mut self, subject: &mut U, ops: I) {
+        for op in ops {
+            self.apply(subject, op);
+        }
+    }
+
+    /// Panics if the subject doesn't hold the same values at the same indices in the same order
+    pub fn check<U: Subject<T>>(&self, subject: &U) {
+        let expected: Vec<(&T, usize)> = self.vals.iter().map(|(val, idx)| (val, *idx)).collect();
+        assert_eq!(subject.entries(), expected, "contents differ!");
+    }
+
+    /// The position modulo len and the index there
+    fn at(&self, pos: usize) -> Option<(usize, usize)> {
+        if self.vals.is_empty() {
+            return None;
+        }
+
+        let pos = pos % self.vals.len();
+        Some((pos, self.vals[pos].1))
+    }
+}
+
+impl<T: Clone + PartialEq + fmt::Debug> Default for Model<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `n` pseudo random ops from `seed`, the same seed gives the same ops.
+/// Pushes and inserts outweigh removals, so the list grows, `Clear` is rare.
+pub fn random_ops(seed: u64, n: usize) -> impl Iterator<Item = Op<u64>> {
+    /* xorshift64*, the state must not be 0 */
+    let mut state = seed | 1;
+    let mut next = move || {
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    };
+
+    (0..n).map(move |_| {
+        let (kind, arg, val) = (next() % 64, next() as usize, next());
+        match kind {
+            0..=11 => Op::PushBack(val),
+            12..=21 => Op::PushFront(val),
+            22..=27 => Op::PopFront,
+            28..=33 => Op::PopBack,
+            34..=43 => Op::InsertAfter(arg, val),
+            44..=52 => Op::InsertBefore(arg, val),
+            53..=62 => Op::Delete(arg),
+            _ => Op::Clear,
+        }
+    })
+}
+
+/// Switches of a `FailPointStorage`, clones control the same storage
+#[derive(Debug, Clone, Default)]
+pub struct FailPoint {
+    shared: Arc<Switches>,
+}
+
+#[derive(Debug)]
+struct Switches {
+    /* `usize::MAX` for none */
+    ceiling: AtomicUsize,
+    fail_allocations: AtomicBool,
+}
+
+/// A `VecStorage` which can be told to stop growing, so `try_reserve` and `try_push_*`
+/// fail when a test wants them to:
+///
+/// ```
+/// use vec_list::testing::FailPointStorage;
+/// use vec_list::VecList;
+/// use vec_list::VecListError;
+///
+/// let (storage, fail_point) = FailPointStorage::new();
+/// let mut list: VecList<u32, _> = VecList::with_storage(storage);
+/// fail_point.set_ceiling(Some(1));
+///
+/// assert!(list.try_push_back(1).is_ok());
+/// assert_eq!(list.try_push_back(2), Err((VecListError::CapacityExceeded, 2)));
+/// ```
+///
+/// The panicking `push_*` panic where they'd grow past the ceiling or allocate while failing,
+/// a storage made by `Storage::with_capacity`, e.g. by `clone`, has switches of its own.
+pub struct FailPointStorage<T> {
+    inner: VecStorage<T>,
+    point: FailPoint,
+}
+
+impl Default for Switches {
+    fn default() -> Self {
+        Self {
+            ceiling: AtomicUsize::new(usize::MAX),
+            fail_allocations: AtomicBool::new(false),
+        }
+    }
+}
+
+impl FailPoint {
+    /// At most `ceiling` slots, deleted ones included, `None` for no limit
+    pub fn set_ceiling(&self, ceiling: Option<usize>) {
+        self.shared
+            .ceiling
+            .store(ceiling.unwrap_or(usize::MAX), Ordering::Relaxed);
+    }
+
+    /// While on, the storage keeps its current allocation and can't grow
+    pub fn fail_allocations(&self, on: bool) {
+        self.shared.fail_allocations.store(on, Ordering::Relaxed);
+    }
+
+    fn ceiling(&self) -> usize {
+        self.shared.ceiling.load(Ordering::Relaxed)
+    }
+
+    fn failing(&self) -> bool {
+        self.shared.fail_allocations.load(Ordering::Relaxed)
+    }
+}
+
+impl<T> FailPointStorage<T> {
+    /// The storage and the switches controlling it, both off
+    pub fn new() -> (Self, FailPoint) {
+        let point = FailPoint::default();
+        let storage = Self {
+            inner: VecStorage::new(),
+            point: point.clone(),
+        };
+        (storage, point)
+    }
+
+    /// How many slots it can hold right now
+    fn limit(&self) -> usize {
+        match self.point.failing() {
+            true => self.inner.capacity().min(self.point.ceiling()),
+            false => self.point.ceiling(),
+        }
+    }
+}
+
+unsafe impl<T> Storage<T> for FailPointStorage<T> {
+    fn with_capacity(cap: usize) -> Self {
+        Self {
+            inner: VecStorage::with_capacity(cap),
+            point: FailPoint::default(),
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    fn max_capacity(&self) -> usize {
+        self.limit().max(self.links().len())
+    }
+
+    fn push(&mut self, link: Link, val: MaybeUninit<T>) {
+        assert!(self.links().len() < self.limit(), "allocation failed!");
+
+        self.inner.push(link, val);
+    }
+
+    fn spare_vals_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        let room = self.limit().saturating_sub(self.links().len());
+        let spare = self.inner.spare_vals_mut();
+        let len = spare.len().min(room);
+        &mut spare[..len]
+    }
+
+    unsafe fn push_link(&mut self, link: Link) {
+        self.inner.push_link(link);
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        if !self.point.failing() {
+            let room = self.point.ceiling().saturating_sub(self.links().len());
+            self.inner.reserve(additional.min(room));
+        }
+    }
+
+    fn try_reserve(&mut self, additional: usize) -> Result<(), VecListError> {
+        match self.links().len().checked_add(additional) {
+            Some(len) if len <= self.limit() => self.inner.try_reserve(additional),
+            _ => Err(VecListError::CapacityExceeded),
+        }
+    }
+
+    fn shrink_to(&mut self, min_capacity: usize) {
+        self.inner.shrink_to(min_capacity);
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.inner.truncate(len);
+    }
+
+    fn links(&self) -> &[Link] {
+        self.inner.links()
+    }
+
+    fn vals(&self) -> &[MaybeUninit<T>] {
+        self.inner.vals()
+    }
+
+    fn parts_mut(&mut self) -> (&mut [Link], &mut [MaybeUninit<T>]) {
+        self.inner.parts_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArrayVecList;
+    use crate::SlotReuse;
+
+    #[test]
+    fn vec_storage_follows_the_model() {
+        for seed in 0..20 {
+            for reuse in [SlotReuse::Lifo, SlotReuse::Fifo] {
+                let mut list = VecList::new();
+                list.set_slot_reuse(reuse);
+                Model::new().run(&mut list, random_ops(seed, 2_000));
+                crate::tests::check(&list);
+            }
+        }
+    }
+
+    #[test]
+    fn array_storage_follows_the_model() {
+        for seed in 0..20 {
+            for reuse in [SlotReuse::Lifo, SlotReuse::Fifo] {
+                /* 2000 ops never hold more than 2000 at once */
+                let mut list = ArrayVecList::<u64, 2_000>::new_inline();
+                list.set_slot_reuse(reuse);
+                Model::new().run(&mut list, random_ops(seed, 2_000));
+                crate::tests::check(&list);
+            }
+        }
+    }
+
+    #[test]
+    fn both_storages_hand_out_the_same_indices() {
+        let mut vec = VecList::new();
+        let mut array = ArrayVecList::<u64, 2_000>::new_inline();
+        let (mut vec_model, mut array_model) = (Model::new(), Model::new());
+
+        for op in random_ops(7, 2_000) {
+            vec_model.apply(&mut vec, op.clone());
+            array_model.apply(&mut array, op);
+            assert!(vec.iter().eq(array.iter()));
+        }
+    }
+
+    fn vals(list: &VecList<u32, FailPointStorage<u32>>) -> Vec<u32> {
+        list.values().copied().collect()
+    }
+
+    #[test]
+    fn try_methods_fail_at_the_ceiling() {
+        let (storage, fail_point) = FailPointStorage::new();
+        let mut list = VecList::with_storage(storage);
+        list.extend([0, 1, 2]);
+        fail_point.set_ceiling(Some(3));
+
+        assert_eq!(list.try_reserve(1), Err(VecListError::CapacityExceeded));
+        assert_eq!(
+            list.try_push_back(3),
+            Err((VecListError::CapacityExceeded, 3))
+        );
+        assert_eq!(
+            list.try_push_front(3),
+            Err((VecListError::CapacityExceeded, 3))
+        );
+        assert_eq!(
+            list.try_insert_after(0, 3),
+            Err((VecListError::CapacityExceeded, 3))
+        );
+        assert_eq!(
+            list.try_insert_before(0, 3),
+            Err((VecListError::CapacityExceeded, 3))
+        );
+        /* a bad index is told apart from a full storage */
+        assert_eq!(
+            list.try_insert_after(9, 3),
+            Err((VecListError::OutOfRange, 3))
+        );
+        assert_eq!(vals(&list), [0, 1, 2]);
+        crate::tests::check(&list);
+
+        /* a deleted slot is room even at the ceiling */
+        list.delete(1);
+        assert_eq!(list.try_push_front(3), Ok(1));
+        assert_eq!(
+            list.try_push_back(4),
+            Err((VecListError::CapacityExceeded, 4))
+        );
+
+        fail_point.set_ceiling(None);
+        assert_eq!(list.try_push_back(4), Ok(3));
+        assert_eq!(vals(&list), [3, 0, 2, 4]);
+        crate::tests::check(&list);
+    }
+
+    #[test]
+    fn try_methods_fail_while_allocations_fail() {
+        let (storage, fail_point) = FailPointStorage::new();
+        let mut list = VecList::with_storage(storage);
+        list.reserve(4);
+        fail_point.fail_allocations(true);
+
+        /* what's allocated already can be filled */
+        let cap = list.vec_cap();
+        for i in 0..cap as u32 {
+            assert_eq!(list.try_push_back(i), Ok(i as usize));
+        }
+        assert_eq!(list.try_reserve(1), Err(VecListError::CapacityExceeded));
+        assert_eq!(
+            list.try_push_back(99),
+            Err((VecListError::CapacityExceeded, 99))
+        );
+        assert_eq!(
+            list.try_insert_before(0, 99),
+            Err((VecListError::CapacityExceeded, 99))
+        );
+        assert_eq!(list.len(), cap);
+        assert_eq!(list.vec_cap(), cap);
+        crate::tests::check(&list);
+
+        fail_point.fail_allocations(false);
+        assert_eq!(list.try_reserve(1), Ok(()));
+        assert_eq!(list.try_push_front(99), Ok(cap));
+        assert_eq!(list.front(), Some((&99, cap)));
+    }
+}
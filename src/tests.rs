@@ -0,0 +1,872 @@
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::rc::Rc;
+
+use crate::occupancy;
+use crate::ArrayStorage;
+use crate::ArrayVecList;
+use crate::Growth;
+use crate::SlotReuse;
+use crate::Storage;
+use crate::VecList;
+use crate::VecListError;
+use crate::VecStorage;
+
+/// Walks both chains and checks every invariant the unsafe code relies on
+pub(crate) fn check<T, S: Storage<T>>(list: &VecList<T, S>) {
+    let links = list.storage.links();
+    assert_eq!(links.len(), list.storage.vals().len());
+    assert!(list.storage.capacity() >= links.len());
+
+    let mut seen = vec![false; links.len()];
+    let (mut cur, mut prev, mut len) = (list.head, None, 0);
+    while let Some(idx) = cur {
+        assert!(links[idx].has_value() && !seen[idx]);
+        assert_eq!(links[idx].prev(), prev);
+        seen[idx] = true;
+        (cur, prev, len) = (links[idx].next(), Some(idx), len + 1);
+    }
+    assert_eq!(prev, list.tail);
+    assert_eq!(len, list.len);
+
+    let (mut cur, mut last, mut deleted) = (list.deleted_tail, None, 0);
+    while let Some(idx) = cur {
+        assert!(links[idx].is_deleted() && !seen[idx]);
+        seen[idx] = true;
+        (cur, last, deleted) = (links[idx].deleted_prev(), Some(idx), deleted + 1);
+    }
+    assert_eq!(last, list.deleted_head);
+    assert_eq!(len + deleted, links.len());
+
+    if list.contiguous {
+        assert_eq!(links.len(), list.len);
+        assert!(list.iter().enumerate().all(|(pos, (_, idx))| pos == idx));
+    }
+    if let Some(occupied) = &list.occupied {
+        for idx in 0..links.len() + 64 {
+            let live = links.get(idx).is_some_and(|link| link.has_value());
+            assert_eq!(occupancy::get(occupied, idx), live);
+        }
+    }
+    assert!(occupancy::ones(&list.marked).all(|idx| links[idx].has_value()));
+}
+
+fn vals<T: Clone, S: Storage<T>>(list: &VecList<T, S>) -> Vec<T> {
+    list.values().cloned().collect()
+}
+
+/// Counts its drops, and panics in `drop` if asked to
+struct Counted(Rc<Cell<usize>>, bool);
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+        if self.1 {
+            panic!("drop panic!");
+        }
+    }
+}
+
+#[test]
+fn push_pop_both_ends() {
+    let mut list = VecList::new();
+    let b = list.push_back(1);
+    let f = list.push_front(0);
+    list.push_back(2);
+    check(&list);
+    assert_eq!(vals(&list), [0, 1, 2]);
+    assert_eq!((list[b], list[f]), (1, 0));
+
+    assert_eq!(list.pop_front(), Some(0));
+    assert_eq!(list.pop_back(), Some(2));
+    assert_eq!(list.pop_back(), Some(1));
+    assert_eq!(list.pop_back(), None);
+    check(&list);
+    assert!(list.is_empty());
+    assert_eq!(list.get(b), None);
+    assert_eq!(list.delete(b), None);
+    assert_eq!(list.delete(100), None);
+}
+
+#[test]
+fn lifo_reuses_the_latest_deleted() {
+    let mut list: VecList<_> = (0..6).collect();
+    for idx in [1, 3, 4] {
+        list.delete(idx);
+    }
+    check(&list);
+
+    let reused: Vec<_> = (0..3).map(|i| list.push_back(i)).collect();
+    assert_eq!(reused, [4, 3, 1]);
+    assert_eq!(list.push_back(9), 6);
+    check(&list);
+}
+
+#[test]
+fn fifo_reuses_the_earliest_deleted() {
+    let mut list: VecList<_> = (0..6).collect();
+    list.set_slot_reuse(SlotReuse::Fifo);
+    for idx in [1, 3, 4] {
+        list.delete(idx);
+    }
+    check(&list);
+
+    let reused: Vec<_> = (0..3).map(|i| list.push_front(i)).collect();
+    assert_eq!(reused, [1, 3, 4]);
+    assert_eq!(list.push_back(9), 6);
+    check(&list);
+}
+
+#[test]
+fn switching_reuse_keeps_the_chain() {
+    let mut list: VecList<_> = (0..6).collect();
+    list.delete(0);
+    list.delete(1);
+    list.set_slot_reuse(SlotReuse::Fifo);
+    list.delete(2);
+    list.delete(3);
+    check(&list);
+
+    /* lifo ones on the reused end, fifo ones behind them in delete order */
+    let reused: Vec<_> = (0..4).map(|i| list.push_back(i)).collect();
+    assert_eq!(reused, [1, 0, 2, 3]);
+    check(&list);
+}
+
+#[test]
+fn vec_storage_slots() {
+    let mut storage = VecStorage::<u32>::with_capacity(2);
+    assert!(storage.capacity() >= 2);
+    assert_eq!(storage.spare_vals_mut().len(), storage.capacity());
+
+    storage.push(
+        crate::Link::value(None, None),
+        std::mem::MaybeUninit::new(7),
+    );
+    assert_eq!(storage.links().len(), 1);
+    assert_eq!(unsafe { storage.vals()[0].assume_init() }, 7);
+
+    storage.spare_vals_mut()[0].write(8);
+    unsafe { storage.push_link(crate::Link::deleted(None)) };
+    assert_eq!(unsafe { storage.vals()[1].assume_init() }, 8);
+    assert!(storage.links()[1].is_deleted());
+
+    storage.truncate(1);
+    assert_eq!((storage.links().len(), storage.vals().len()), (1, 1));
+    storage.clear();
+    assert!(storage.links().is_empty() && storage.vals().is_empty());
+
+    assert_eq!(
+        storage.try_reserve(usize::MAX),
+        Err(VecListError::CapacityExceeded)
+    );
+    assert_eq!(storage.try_reserve(16), Ok(()));
+    assert!(storage.capacity() >= 16);
+}
+
+#[test]
+fn vec_storage_growth() {
+    let mut list = VecList::new();
+    list.set_growth(Growth::Exact);
+    for i in 0..5 {
+        list.push_back(i);
+        assert_eq!(list.vec_cap(), i + 1);
+    }
+
+    let mut list = VecList::new();
+    list.set_growth(Growth::Add(4));
+    for i in 0..9 {
+        list.push_back(i);
+    }
+    assert_eq!(list.vec_cap(), 12);
+
+    let mut list = VecList::new();
+    list.set_growth(Growth::Custom(|cap| cap * 3));
+    for i in 0..4 {
+        list.push_back(i);
+    }
+    assert_eq!(list.vec_cap(), 9);
+    check(&list);
+}
+
+#[test]
+fn into_empty_keeps_the_buffer() {
+    let mut list = VecList::<u32>::with_capacity(64);
+    list.extend(0..10);
+    let cap = list.vec_cap();
+
+    let list = list.into_empty::<i32>();
+    assert!(list.is_empty());
+    assert_eq!(list.vec_cap(), cap);
+
+    let list = list.into_empty::<u64>();
+    assert!(list.vec_cap() < cap);
+}
+
+#[test]
+fn array_storage_slots() {
+    let mut storage = ArrayStorage::<u32, 2>::new();
+    assert_eq!((storage.capacity(), storage.max_capacity()), (2, 2));
+    assert_eq!(storage.spare_vals_mut().len(), 2);
+
+    storage.push(
+        crate::Link::value(None, None),
+        std::mem::MaybeUninit::new(1),
+    );
+    storage.push(
+        crate::Link::value(None, None),
+        std::mem::MaybeUninit::new(2),
+    );
+    assert!(storage.spare_vals_mut().is_empty());
+    assert_eq!(storage.try_reserve(1), Err(VecListError::CapacityExceeded));
+
+    storage.truncate(1);
+    assert_eq!(storage.try_reserve(1), Ok(()));
+    assert_eq!(storage.links().len(), 1);
+    storage.clear();
+    assert!(storage.vals().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "storage is full!")]
+fn array_storage_push_past_n() {
+    let mut list = ArrayVecList::<u32, 2>::new_inline();
+    for i in 0..3 {
+        list.push_back(i);
+    }
+}
+
+#[test]
+fn array_vec_list_reuses_its_slots() {
+    let mut list = ArrayVecList::<u32, 3>::new_inline();
+    for i in 0..3 {
+        list.push_back(i);
+    }
+    assert_eq!(list.push_back_within_capacity(3), Err(3));
+    assert_eq!(list.push_front_within_capacity(3), Err(3));
+    assert_eq!(list.try_reserve(1), Err(VecListError::CapacityExceeded));
+
+    for round in 0..10 {
+        list.pop_front();
+        list.push_back(round);
+        check(&list);
+        assert_eq!(list.cap(), 3);
+    }
+    assert_eq!(vals(&list), [7, 8, 9]);
+
+    let counter = Rc::new(Cell::new(0));
+    let mut list = ArrayVecList::<Counted, 4>::new_inline();
+    for _ in 0..4 {
+        list.push_back(Counted(counter.clone(), false));
+    }
+    list.delete(1);
+    assert_eq!(counter.get(), 1);
+    drop(list);
+    assert_eq!(counter.get(), 4);
+}
+
+#[test]
+fn drops_each_value_once() {
+    let counter = Rc::new(Cell::new(0));
+    let mut list = VecList::new();
+    for _ in 0..10 {
+        list.push_back(Counted(counter.clone(), false));
+    }
+
+    drop(list.delete(3));
+    drop(list.pop_front());
+    drop(list.delete_fill(5));
+    assert_eq!(counter.get(), 3);
+
+    list.optimize_for_iteration();
+    assert_eq!(counter.get(), 3);
+    let left = list.len();
+
+    list.clear();
+    assert_eq!(counter.get(), 3 + left);
+    for _ in 0..3 {
+        list.push_back(Counted(counter.clone(), false));
+    }
+    drop(list);
+    assert_eq!(counter.get(), 3 + left + 3);
+}
+
+#[test]
+fn clear_survives_a_panicking_drop() {
+    let counter = Rc::new(Cell::new(0));
+    let mut list = VecList::new();
+    list.push_back(Counted(counter.clone(), false));
+    list.push_back(Counted(counter.clone(), true));
+    list.push_back(Counted(counter.clone(), false));
+
+    let cleared = panic::catch_unwind(AssertUnwindSafe(|| list.clear()));
+    assert!(cleared.is_err());
+    /* the one after the panicking one leaks, nothing is dropped twice */
+    assert_eq!(counter.get(), 2);
+    assert!(list.is_empty() && list.cap() == 0);
+    check(&list);
+
+    list.push_back(Counted(counter.clone(), false));
+    drop(list);
+    assert_eq!(counter.get(), 3);
+}
+
+#[test]
+fn extend_links_the_run_even_if_the_iterator_panics() {
+    /* claims an exact size, so `extend` takes the one pass path */
+    struct Panicky(u32);
+
+    impl Iterator for Panicky {
+        type Item = u32;
+
+        fn next(&mut self) -> Option<u32> {
+            self.0 += 1;
+            if self.0 == 4 {
+                panic!("iterator panic!");
+            }
+            Some(self.0)
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (5, Some(5))
+        }
+    }
+
+    let mut list: VecList<u32> = (10..12).collect();
+    let extended = panic::catch_unwind(AssertUnwindSafe(|| list.extend(Panicky(0))));
+    assert!(extended.is_err());
+    check(&list);
+    assert_eq!(vals(&list), [10, 11, 1, 2, 3]);
+
+    list.push_back(4);
+    check(&list);
+    assert_eq!(vals(&list), [10, 11, 1, 2, 3, 4]);
+}
+
+#[test]
+fn extend_drops_nothing_twice() {
+    let counter = Rc::new(Cell::new(0));
+    let mut list = VecList::new();
+    list.extend((0..5).map(|_| Counted(counter.clone(), false)));
+    list.delete(2);
+    /* a deleted slot, so this one goes through `push_back` */
+    list.extend((0..2).map(|_| Counted(counter.clone(), false)));
+    check(&list);
+    assert_eq!(list.len(), 6);
+    drop(list);
+    assert_eq!(counter.get(), 7);
+}
+
+#[test]
+fn contiguous_fast_paths() {
+    let mut list: VecList<_> = (0..8).collect();
+    assert!(list.is_contiguous());
+    assert!(list.contains(&5) && !list.contains(&8));
+    assert_eq!(list.index_of(&5), Some(5));
+    assert!(list.is_sorted());
+
+    list.extend_from_slice(&[8, 9]);
+    assert!(list.is_contiguous());
+    check(&list);
+
+    list.delete(2);
+    assert!(!list.is_contiguous());
+    assert!(!list.contains(&2));
+    assert_eq!(list.index_of(&5), Some(5));
+    check(&list);
+
+    let front = list.push_front(10);
+    assert!(!list.is_sorted());
+    assert_eq!(list.index_of(&10), Some(front));
+
+    let remap = list.optimize_for_iteration();
+    assert!(list.is_contiguous());
+    check(&list);
+    assert_eq!(remap[front], Some(0));
+    assert_eq!(remap[0], Some(1));
+    assert_eq!(remap[9], Some(9));
+    assert_eq!(vals(&list), [10, 0, 1, 3, 4, 5, 6, 7, 8, 9]);
+    assert_eq!(list.index_of(&3), Some(3));
+    assert!(!list.contains(&2));
+
+    /* the last slot is the tail, so nothing moves and it stays contiguous */
+    assert_eq!(list.delete_fill(9), Some((9, None)));
+    assert!(list.is_contiguous());
+    check(&list);
+    assert_eq!(list.push_back(9), 9);
+    assert!(list.is_contiguous());
+
+    assert_eq!(list.delete_fill(0), Some((10, Some(9))));
+    assert!(!list.is_contiguous());
+    check(&list);
+    assert_eq!(list[0], 9);
+    assert_eq!(vals(&list), [0, 1, 3, 4, 5, 6, 7, 8, 9]);
+    assert!(list.contains(&9) && !list.contains(&10));
+    assert!(list.is_sorted());
+}
+
+#[test]
+fn delete_fill_moves_the_last_slot() {
+    let mut list: VecList<_> = (0..6).collect();
+    list.set_occupancy_tracking(true);
+    list.swap_links(1, 5);
+    list.mark_for_delete(5);
+    assert_eq!(vals(&list), [0, 5, 2, 3, 4, 1]);
+
+    /* the moved one keeps its place in the list and its mark */
+    assert_eq!(list.delete_fill(2), Some((2, Some(5))));
+    check(&list);
+    assert_eq!(list.cap(), 5);
+    assert_eq!(vals(&list), [0, 5, 3, 4, 1]);
+    assert_eq!(list[2], 5);
+    assert!(list.is_marked(2) && !list.is_marked(5));
+
+    /* head and tail moving */
+    assert_eq!(list.delete_fill(0), Some((0, Some(4))));
+    check(&list);
+    assert_eq!(list.front(), Some((&5, 2)));
+    assert_eq!(list.delete_fill(1), Some((1, Some(3))));
+    check(&list);
+    assert_eq!(list.back(), Some((&4, 0)));
+    assert_eq!(vals(&list), [5, 3, 4]);
+
+    /* a deleted last slot is dropped first, then the last live one moves */
+    list.push_back(6);
+    list.delete(3);
+    assert_eq!(list.delete_fill(1), Some((3, Some(2))));
+    check(&list);
+    assert_eq!(list.cap(), 2);
+    assert_eq!(list[1], 5);
+    assert_eq!(list.delete_fill(2), None);
+    assert_eq!(list.delete_fill(100), None);
+    assert_eq!(vals(&list), [5, 4]);
+}
+
+#[test]
+fn delete_fill_drops_deleted_slots_at_the_end() {
+    for (reuse, reused) in [(SlotReuse::Lifo, [4, 2]), (SlotReuse::Fifo, [2, 4])] {
+        let mut list: VecList<u32> = (0..10).collect();
+        list.set_slot_reuse(reuse);
+        list.set_occupancy_tracking(true);
+        /* the chain mixes slots which stay with ones at the end */
+        for idx in [2, 9, 4, 8] {
+            list.delete(idx);
+        }
+
+        assert_eq!(list.delete_fill(0), Some((0, Some(7))));
+        check(&list);
+        assert_eq!(list.cap(), 7);
+        /* it keeps its place in the list */
+        assert_eq!(list.back(), Some((&7, 0)));
+        assert_eq!(vals(&list), [1, 3, 5, 6, 7]);
+
+        /* the slots left on the chain are reused in the same order as before */
+        assert_eq!(list.push_back(10), reused[0]);
+        assert_eq!(list.push_back(11), reused[1]);
+        assert_eq!(list.push_back(12), 7);
+        check(&list);
+    }
+
+    /* the one deleted is the last live one */
+    let mut list: VecList<u32> = (0..3).collect();
+    list.delete(2);
+    list.delete(1);
+    assert_eq!(list.delete_fill(0), Some((0, None)));
+    check(&list);
+    assert_eq!(list.cap(), 0);
+    assert_eq!(list.push_back(3), 0);
+}
+
+#[test]
+fn commit_spare_links_the_written_slots() {
+    let mut list = VecList::<u32>::new();
+    list.push_back(0);
+    list.reserve(4);
+
+    let spare = list.spare_slots_mut();
+    assert!(spare.len() >= 4);
+    for (i, slot) in spare[..3].iter_mut().enumerate() {
+        slot.write(i as u32 + 1);
+    }
+    let added = unsafe { list.commit_spare(3) };
+    assert_eq!(added, 1..4);
+    check(&list);
+    assert_eq!(vals(&list), [0, 1, 2, 3]);
+    assert!(list.is_contiguous());
+
+    assert_eq!(unsafe { list.commit_spare(0) }, 4..4);
+    check(&list);
+
+    let mut list = ArrayVecList::<u32, 4>::new_inline();
+    for slot in list.spare_slots_mut() {
+        slot.write(7);
+    }
+    assert_eq!(unsafe { list.commit_spare(4) }, 0..4);
+    check(&list);
+    assert_eq!(vals(&list), [7; 4]);
+    assert!(list.spare_slots_mut().is_empty());
+}
+
+#[test]
+fn occupancy_tracking_follows_every_change() {
+    let mut list: VecList<_> = (0..200).collect();
+    list.set_occupancy_tracking(true);
+    for idx in (0..200).step_by(3) {
+        list.delete(idx);
+    }
+    check(&list);
+    list.delete_fill(10);
+    list.push_front(1000);
+    list.insert_after(1, 1001);
+    check(&list);
+
+    let mut unordered: Vec<_> = list.iter_unordered().map(|(_, idx)| idx).collect();
+    let mut ordered: Vec<_> = list.iter().map(|(_, idx)| idx).collect();
+    assert!(unordered.is_sorted());
+    ordered.sort_unstable();
+    unordered.sort_unstable();
+    assert_eq!(unordered, ordered);
+
+    list.optimize_for_iteration();
+    check(&list);
+    list.clear();
+    check(&list);
+}
+
+#[test]
+fn clones_keep_indices() {
+    let mut list: VecList<u32> = (0..10).collect();
+    list.delete(4);
+    list.delete(7);
+    list.push_front(20);
+
+    for cloned in [list.clone(), list.clone_copy()] {
+        check(&cloned);
+        assert_eq!(vals(&cloned), vals(&list));
+        assert!(list.iter().all(|(val, idx)| cloned.get(idx) == Some(val)));
+        assert_eq!(cloned.deleted_tail, list.deleted_tail);
+    }
+
+    let mut array = ArrayVecList::<u32, 4>::new_inline();
+    array.extend(0..3);
+    array.delete(1);
+    let cloned = array.clone_copy();
+    check(&cloned);
+    assert_eq!(vals(&cloned), [0, 2]);
+}
+
+#[test]
+fn try_reserve_stops_at_max_slots() {
+    let mut list = VecList::<u8>::new();
+    assert_eq!(
+        list.try_reserve(crate::link::MAX_SLOTS + 1),
+        Err(VecListError::CapacityExceeded)
+    );
+    assert_eq!(list.try_reserve(8), Ok(()));
+    assert!(list.vec_cap() >= 8);
+}
+
+#[test]
+fn try_methods_give_the_value_back() {
+    let mut list = ArrayVecList::<u32, 3>::new_inline();
+    assert_eq!(list.try_push_back(1), Ok(0));
+    assert_eq!(list.try_push_front(0), Ok(1));
+    assert_eq!(list.try_insert_after(0, 2), Ok(2));
+    assert_eq!(
+        list.try_push_back(3),
+        Err((VecListError::CapacityExceeded, 3))
+    );
+    assert_eq!(
+        list.try_push_front(3),
+        Err((VecListError::CapacityExceeded, 3))
+    );
+    assert_eq!(
+        list.try_insert_before(0, 3),
+        Err((VecListError::CapacityExceeded, 3))
+    );
+    assert_eq!(vals(&list), [0, 1, 2]);
+
+    /* a deleted slot is room again */
+    assert_eq!(list.try_delete(2), Ok(2));
+    assert_eq!(list.try_delete(2), Err(VecListError::SlotDeleted));
+    assert_eq!(
+        list.try_insert_after(2, 3),
+        Err((VecListError::SlotDeleted, 3))
+    );
+    assert_eq!(
+        list.try_insert_before(5, 3),
+        Err((VecListError::OutOfRange, 3))
+    );
+    assert_eq!(list.try_insert_before(0, 3), Ok(2));
+    assert_eq!(vals(&list), [0, 3, 1]);
+    check(&list);
+
+    assert_eq!(list.try_replace(2, 4), Ok(3));
+    assert_eq!(list.try_replace(7, 5), Err((VecListError::OutOfRange, 5)));
+    assert_eq!(list.try_get(2), Ok(&4));
+    assert_eq!(list.try_get(7), Err(VecListError::OutOfRange));
+}
+
+#[test]
+fn delete_many_matches_deleting_one_by_one() {
+    for reuse in [SlotReuse::Lifo, SlotReuse::Fifo] {
+        let mut batched: VecList<u32> = (0..300).collect();
+        batched.set_slot_reuse(reuse);
+        batched.set_occupancy_tracking(true);
+        batched.set_position_index(true);
+        /* some slots are on the deleted chain already */
+        for idx in [5, 150, 299] {
+            batched.delete(idx);
+        }
+        for val in 300..310 {
+            batched.push_front(val);
+        }
+        batched.mark_for_delete(10);
+        let mut single = batched.clone();
+
+        /* runs of neighbors, the head, the tail, repeats, deleted and out of range ones */
+        let mut indices: Vec<usize> = (20..60).chain([0, 1, 2, 298, 297, 100, 102, 101]).collect();
+        indices.extend([5, 40, 10, 1000]);
+        indices.extend(single.iter().take(4).map(|(_, idx)| idx));
+        indices.extend((130..170).step_by(2));
+
+        let mut sorted = indices.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        let deleted = sorted
+            .iter()
+            .filter(|&&idx| single.delete(idx).is_some())
+            .count();
+
+        assert_eq!(batched.delete_many(&indices), deleted);
+        check(&batched);
+        assert_eq!(vals(&batched), vals(&single));
+        assert_eq!(batched.deleted_tail, single.deleted_tail);
+        assert_eq!(batched.deleted_head, single.deleted_head);
+        assert!(!batched.is_marked(10));
+        for (pos, (_, idx)) in batched.iter().enumerate() {
+            assert_eq!(batched.position_of(idx), Some(pos));
+        }
+        assert!(batched.positions_synced());
+
+        /* the slots come back in the same order */
+        for val in 0..deleted as u32 + 5 {
+            assert_eq!(batched.push_back(val), single.push_back(val));
+        }
+        check(&batched);
+        assert_eq!(batched.delete_many(&[]), 0);
+    }
+}
+
+#[test]
+fn delete_many_survives_a_panicking_drop() {
+    let counter = Rc::new(Cell::new(0));
+    let mut list = VecList::new();
+    for i in 0..5 {
+        list.push_back(Counted(counter.clone(), i == 1));
+    }
+
+    let deleted = panic::catch_unwind(AssertUnwindSafe(|| list.delete_many(&[0, 1, 2, 4])));
+    assert!(deleted.is_err());
+    /* the rest of the batch is still dropped, nothing twice */
+    assert_eq!(counter.get(), 4);
+    assert_eq!(list.len(), 1);
+    check(&list);
+
+    drop(list);
+    assert_eq!(counter.get(), 5);
+}
+
+#[test]
+fn swap_links_in_every_position() {
+    /* (a, b, order after the swap), with 0 the head and 4 the tail */
+    let cases = [
+        (2, 2, [0, 1, 2, 3, 4]),
+        (1, 2, [0, 2, 1, 3, 4]),
+        (2, 1, [0, 2, 1, 3, 4]),
+        (0, 1, [1, 0, 2, 3, 4]),
+        (4, 3, [0, 1, 2, 4, 3]),
+        (0, 4, [4, 1, 2, 3, 0]),
+        (4, 0, [4, 1, 2, 3, 0]),
+        (0, 2, [2, 1, 0, 3, 4]),
+        (3, 4, [0, 1, 2, 4, 3]),
+    ];
+    for (a, b, order) in cases {
+        let mut list: VecList<u32> = (0..5).collect();
+        list.swap_links(a, b);
+        check(&list);
+        assert_eq!(vals(&list), order);
+        /* the values stay in their slots */
+        assert!(list.iter().all(|(val, idx)| *val as usize == idx));
+
+        list.swap_links(b, a);
+        check(&list);
+        assert_eq!(vals(&list), [0, 1, 2, 3, 4]);
+    }
+
+    /* a two element list is all head and tail */
+    let mut list: VecList<u32> = (0..2).collect();
+    list.swap_links(1, 0);
+    check(&list);
+    assert_eq!(vals(&list), [1, 0]);
+    assert_eq!((list.front(), list.back()), (Some((&1, 1)), Some((&0, 0))));
+
+    /* a single one swaps with itself */
+    let mut list: VecList<u32> = (0..1).collect();
+    list.swap_links(0, 0);
+    check(&list);
+    assert_eq!(vals(&list), [0]);
+}
+
+#[test]
+#[should_panic(expected = "invalid key!")]
+fn swap_links_with_a_deleted_slot_panics() {
+    let mut list: VecList<u32> = (0..3).collect();
+    list.delete(1);
+    list.swap_links(0, 1);
+}
+
+/// Ordered by the key only, the tag tells equal ones apart
+#[derive(Debug, Clone, Copy)]
+struct Keyed(u32, char);
+
+impl PartialEq for Keyed {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Keyed {}
+
+impl PartialOrd for Keyed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Keyed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+#[test]
+fn merge_k_of_empty_and_single_lists() {
+    assert!(VecList::<u32>::merge_k(Vec::new()).is_empty());
+    assert!(VecList::<u32>::merge_k(vec![VecList::new(), VecList::new()]).is_empty());
+
+    let merged = VecList::merge_k(vec![(0..5).collect()]);
+    check(&merged);
+    assert_eq!(vals(&merged), [0, 1, 2, 3, 4]);
+
+    let merged = VecList::merge_k(vec![
+        VecList::new(),
+        [1, 3].into_iter().collect(),
+        VecList::new(),
+    ]);
+    check(&merged);
+    assert_eq!(vals(&merged), [1, 3]);
+}
+
+#[test]
+fn merge_k_keeps_equal_ones_in_list_order() {
+    let lists = vec![
+        [Keyed(1, 'a'), Keyed(2, 'a'), Keyed(2, 'b')]
+            .into_iter()
+            .collect(),
+        [Keyed(0, 'c'), Keyed(2, 'c')].into_iter().collect(),
+        [Keyed(1, 'd'), Keyed(2, 'd'), Keyed(3, 'd')]
+            .into_iter()
+            .collect(),
+    ];
+    let merged = VecList::merge_k(lists);
+    check(&merged);
+
+    let tags: String = merged.values().map(|keyed| keyed.1).collect();
+    assert_eq!(tags, "cadabcdd");
+    assert!(merged.is_sorted());
+}
+
+#[test]
+fn merge_k_walks_fragmented_lists_in_list_order() {
+    let mut first: VecList<u32> = [9, 1, 9, 4, 7].into_iter().collect();
+    first.delete(0);
+    first.delete(2);
+    /* reuses a deleted slot at the front, slot order is no longer list order */
+    first.push_front(0);
+    let mut second: VecList<u32> = [2, 9, 5].into_iter().collect();
+    second.delete(1);
+
+    let merged = VecList::merge_k(vec![first, second]);
+    check(&merged);
+    assert_eq!(vals(&merged), [0, 1, 2, 4, 5, 7]);
+    assert_eq!(merged.cap(), merged.len());
+}
+
+#[test]
+fn interleave_unequal_and_empty_lists() {
+    /* (self, other, result) */
+    let cases: [(&[u32], &[u32], &[u32]); 5] = [
+        (&[0, 2, 4], &[1, 3, 5], &[0, 1, 2, 3, 4, 5]),
+        (&[0, 2], &[1, 3, 5, 6], &[0, 1, 2, 3, 5, 6]),
+        (&[0, 2, 4, 5], &[1, 3], &[0, 1, 2, 3, 4, 5]),
+        (&[], &[0, 1], &[0, 1]),
+        (&[0, 1], &[], &[0, 1]),
+    ];
+    for (this, other, result) in cases {
+        let mut list: VecList<u32> = this.iter().copied().collect();
+        let before: Vec<(u32, usize)> = list.iter().map(|(val, idx)| (*val, idx)).collect();
+
+        list.interleave(other.iter().copied().collect::<VecList<u32>>());
+        check(&list);
+        assert_eq!(vals(&list), result);
+        /* the ones of `self` keep their indices */
+        for (val, idx) in before {
+            assert_eq!(list[idx], val);
+        }
+    }
+
+    let mut list: VecList<u32> = VecList::new();
+    list.interleave(VecList::<u32>::new());
+    check(&list);
+    assert!(list.is_empty());
+}
+
+#[test]
+fn split_n_spreads_the_rest_over_the_first_ones() {
+    /* (len, n, piece lengths) */
+    let cases: [(u32, usize, &[usize]); 5] = [
+        (6, 3, &[2, 2, 2]),
+        (7, 3, &[3, 2, 2]),
+        (8, 3, &[3, 3, 2]),
+        (2, 4, &[1, 1, 0, 0]),
+        (0, 2, &[0, 0]),
+    ];
+    for (len, n, lens) in cases {
+        let mut list: VecList<u32> = (0..len).collect();
+        /* fragmented, the pieces follow list order */
+        if len > 1 {
+            list.swap_links(0, len as usize - 1);
+        }
+        let order = vals(&list);
+
+        let pieces = list.split_n(n);
+        assert_eq!(pieces.iter().map(VecList::len).collect::<Vec<_>>(), lens);
+        for piece in &pieces {
+            check(piece);
+        }
+        assert_eq!(pieces.iter().flat_map(vals).collect::<Vec<_>>(), order);
+    }
+
+    let pieces = (0..3).collect::<ArrayVecList<u32, 4>>().split_n(2);
+    assert_eq!(vals(&pieces[0]), [0, 1]);
+    assert_eq!(vals(&pieces[1]), [2]);
+}
+
+#[test]
+#[should_panic(expected = "n can't be zero!")]
+fn split_n_into_zero_panics() {
+    (0..3).collect::<VecList<u32>>().split_n(0);
+}
@@ -0,0 +1,41 @@
+use js_sys::Array;
+use wasm_bindgen::JsValue;
+
+use crate::Storage;
+use crate::VecList;
+
+impl<T: Clone + Into<JsValue>, S: Storage<T>> VecList<T, S> {
+    /// O(n), a JS array of the values front to back
+    pub fn to_js_array(&self) -> Array {
+        self.values()
+            .cloned()
+            .map(Into::into)
+            .collect::<Vec<JsValue>>()
+            .into_iter()
+            .collect()
+    }
+}
+
+impl<T: Into<JsValue>, S: Storage<T>> From<VecList<T, S>> for Array {
+    /// O(n), front to back
+    fn from(list: VecList<T, S>) -> Self {
+        let array = Array::new_with_length(list.len() as u32);
+        for (pos, val) in list.into_iter().enumerate() {
+            array.set(pos as u32, val.into());
+        }
+        array
+    }
+}
+
+impl<T: TryFrom<JsValue>, S: Storage<T>> TryFrom<&Array> for VecList<T, S> {
+    type Error = T::Error;
+
+    /// O(n), pushes the items in order, stops at the first one which doesn't convert
+    fn try_from(array: &Array) -> Result<Self, Self::Error> {
+        let mut list = Self::default();
+        for item in array.iter() {
+            list.push_back(T::try_from(item)?);
+        }
+        Ok(list)
+    }
+}
@@ -0,0 +1,176 @@
+use crate::BoundedList;
+use crate::Iter;
+
+/// The last `cap` samples in arrival order, plus a sorted copy of them,
+/// so order statistics are O(1) and a new sample is O(cap) at worst.
+#[derive(Debug, Clone)]
+pub struct SlidingWindow<T> {
+    samples: BoundedList<T>,
+    /* the same values as `samples`, sorted */
+    sorted: Vec<T>,
+}
+
+impl<T: Ord + Clone> SlidingWindow<T> {
+    pub fn new(cap: usize) -> Self {
+        Self {
+            samples: BoundedList::new(cap),
+            sorted: Vec::with_capacity(cap),
+        }
+    }
+
+    /// Returns the sample which expired to make room, the new one itself if cap is 0
+    pub fn push(&mut self, val: T) -> Option<T> {
+        if self.samples.capacity() == 0 {
+            return Some(val);
+        }
+
+        /* expire by hand, so `add` never evicts behind `sorted`'s back */
+        let expired = if self.samples.is_full() {
+            self.samples.pop_front()
+        } else {
+            None
+        };
+        if let Some(expired) = &expired {
+            let pos = self
+                .sorted
+                .binary_search(expired)
+                .expect("sorted samples are out of sync!");
+            self.sorted.remove(pos);
+        }
+
+        let pos = self.sorted.partition_point(|x| x <= &val);
+        self.sorted.insert(pos, val.clone());
+        self.samples.add(val);
+
+        expired
+    }
+
+    /// O(1), the lower one if there are two
+    pub fn median(&self) -> Option<&T> {
+        let n = self.sorted.len();
+        if n == 0 {
+            return None;
+        }
+
+        self.sorted.get((n - 1) / 2)
+    }
+
+    /// O(1), nearest rank, panics if `p` is not in `0.0..=100.0`, like NaN
+    pub fn percentile(&self, p: f64) -> Option<&T> {
+        assert!((0.0..=100.0).contains(&p), "`p` is not in 0..=100!");
+
+        let n = self.sorted.len();
+        let rank = (p / 100.0 * n as f64).ceil() as usize;
+        self.sorted.get(rank.max(1) - 1)
+    }
+
+    /// O(1)
+    pub fn min(&self) -> Option<&T> {
+        self.sorted.first()
+    }
+
+    /// O(1)
+    pub fn max(&self) -> Option<&T> {
+        self.sorted.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.samples.capacity()
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.sorted.clear();
+    }
+
+    /// Oldest first
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.samples.iter()
+    }
+
+    /// Smallest first
+    pub fn sorted(&self) -> &[T] {
+        &self.sorted
+    }
+
+    pub fn as_bounded(&self) -> &BoundedList<T> {
+        &self.samples
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(vals: &[u32]) -> SlidingWindow<u32> {
+        let mut window = SlidingWindow::new(vals.len());
+        for &val in vals {
+            window.push(val);
+        }
+        window
+    }
+
+    #[test]
+    fn median_is_the_lower_middle() {
+        assert_eq!(window(&[]).median(), None);
+        assert_eq!(window(&[5]).median(), Some(&5));
+        assert_eq!(window(&[3, 1, 2]).median(), Some(&2));
+        assert_eq!(window(&[4, 1, 3, 2]).median(), Some(&2));
+        assert_eq!(window(&[7, 7, 1, 9]).median(), Some(&7));
+    }
+
+    #[test]
+    fn percentile_is_the_nearest_rank() {
+        let window = window(&[15, 20, 35, 40, 50]);
+        assert_eq!(window.percentile(0.0), Some(&15));
+        assert_eq!(window.percentile(5.0), Some(&15));
+        assert_eq!(window.percentile(30.0), Some(&20));
+        assert_eq!(window.percentile(40.0), Some(&20));
+        assert_eq!(window.percentile(50.0), Some(&35));
+        assert_eq!(window.percentile(100.0), Some(&50));
+
+        let even = self::window(&[1, 2, 3, 4]);
+        assert_eq!(even.percentile(50.0), Some(&2));
+        assert_eq!(even.percentile(51.0), Some(&3));
+        assert_eq!(self::window(&[]).percentile(50.0), None);
+    }
+
+    #[test]
+    fn percentile_rejects_p_out_of_range() {
+        let window = window(&[1, 2]);
+        for p in [-0.1, 100.1, f64::NAN, f64::INFINITY] {
+            let res = std::panic::catch_unwind(|| window.percentile(p).copied());
+            assert!(res.is_err(), "{} is accepted", p);
+        }
+    }
+
+    #[test]
+    fn old_samples_slide_out() {
+        let mut window = SlidingWindow::new(3);
+        assert_eq!(window.push(9), None);
+        window.push(1);
+        window.push(5);
+        assert_eq!(window.median(), Some(&5));
+
+        assert_eq!(window.push(2), Some(9));
+        assert_eq!(window.sorted(), [1, 2, 5]);
+        assert_eq!(window.median(), Some(&2));
+        assert_eq!((window.min(), window.max()), (Some(&1), Some(&5)));
+
+        assert_eq!(window.push(2), Some(1));
+        assert_eq!(window.push(8), Some(5));
+        assert_eq!(window.sorted(), [2, 2, 8]);
+        assert!(window.iter().map(|(val, _)| *val).eq([2, 2, 8]));
+        assert_eq!(window.percentile(100.0), Some(&8));
+
+        assert_eq!(SlidingWindow::new(0).push(4), Some(4));
+    }
+}